@@ -1,40 +1,67 @@
-use crate::models::github::Repository;
-use crate::services::github::{extract_repo_links, extract_repo_links_with_categories, GitHubClient};
-use tauri::State;
-use std::sync::Mutex;
-use std::collections::HashMap;
+use crate::models::github::{AwesomeEntry, CrawledEntry, Forge, HealthScoreResult, ImportedRepositories, LinkCheckResult, LintDiagnostic, ListSection, MaintenanceReport, Repository, RateLimitStatus, RepositoryFetchResult, RepositoryTrend, ScanComparison, ScanCheckpointSummary, ScanProgress, ScanResult, ScanStarted, ScanSummary};
+use crate::services::added_dates::{load_additions, save_additions};
+use crate::services::checkpoint::{delete_checkpoint, load_checkpoint, save_checkpoint, CompletedEntry, ScanCheckpoint};
+use crate::services::crawler::{crawl_awesome_lists, CrawlOptions};
+use crate::services::export::{
+    check_delimiter, write_csv, write_html, write_json, write_markdown, write_ndjson, write_sqlite, CsvOptions,
+    ExportColumn, ExportSort, HtmlOptions, MarkdownOptions,
+};
+use crate::services::forges::ForgeClient;
+use crate::services::github::{extract_repo_links, extract_repo_links_with_categories, parse_github_url, GitHubClient};
+use crate::services::health::score_repositories;
+use crate::services::history::ScanHistory;
+use crate::services::import::{import_csv, import_json, import_ndjson};
+use crate::services::http_cache::HttpCache;
+use crate::services::links::{check_links, collect_link_targets, DEFAULT_LINK_CONCURRENCY, HOST_DELAY};
+use crate::services::lint::lint_awesome_list;
+use crate::services::local::read_local_readme;
+use crate::services::maintenance::build_maintenance_report;
+use crate::services::markdown::{parse_awesome_list, parse_awesome_list_tree};
+use crate::services::rate_limit::{quota_wait_seconds, unix_now, RateLimitTracker};
+use crate::services::scanner::{
+    build_scan_result, enrich_repository, entry_key, scan_entries, EnrichOptions, DEFAULT_CONCURRENCY,
+};
+use crate::services::trends::{build_trends, compare_metrics};
+use tauri::{AppHandle, Emitter, Manager, State};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 pub struct AppState {
     pub github_token: Mutex<Option<String>>,
+    pub gitea_hosts: Mutex<Vec<String>>,
+    pub last_scan: Mutex<Option<ScanResult>>,
+    /// Cancellation flags of the scans currently running, by scan ID
+    pub running_scans: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    pub rate_limit: Arc<RateLimitTracker>,
+    /// GitHub GET responses cached on disk, revalidated with ETags
+    pub http_cache: Arc<HttpCache>,
+    pub history: ScanHistory,
 }
 
+/// Completed repositories are checkpointed after this many fetches
+const CHECKPOINT_INTERVAL: usize = 10;
+
 #[tauri::command]
 pub async fn fetch_awesome_list(
     url: String,
+    force_refresh: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<Vec<(String, String)>, String> {
-    // Parse the GitHub URL to extract owner and repo
-    let url_parts: Vec<&str> = url
-        .trim_end_matches('/')
-        .split('/')
-        .collect();
-
-    if url_parts.len() < 2 {
-        return Err("Invalid GitHub URL".to_string());
-    }
-
-    let owner = url_parts[url_parts.len() - 2];
-    let repo = url_parts[url_parts.len() - 1];
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
 
     // Get the token from state
     let token = state.github_token.lock().unwrap().clone();
 
     // Create GitHub client
-    let client = GitHubClient::new(token);
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
 
     // Fetch README
     let readme = client
-        .fetch_readme(owner, repo)
+        .fetch_list_markdown(&list_url)
         .await
         .map_err(|e| format!("Failed to fetch README: {}", e))?;
 
@@ -47,30 +74,22 @@ pub async fn fetch_awesome_list(
 #[tauri::command]
 pub async fn fetch_awesome_list_with_categories(
     url: String,
+    force_refresh: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<Vec<(String, String, String)>, String> {
-    // Parse the GitHub URL to extract owner and repo
-    let url_parts: Vec<&str> = url
-        .trim_end_matches('/')
-        .split('/')
-        .collect();
-
-    if url_parts.len() < 2 {
-        return Err("Invalid GitHub URL".to_string());
-    }
-
-    let owner = url_parts[url_parts.len() - 2];
-    let repo = url_parts[url_parts.len() - 1];
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
 
     // Get the token from state
     let token = state.github_token.lock().unwrap().clone();
 
     // Create GitHub client
-    let client = GitHubClient::new(token);
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
 
     // Fetch README
     let readme = client
-        .fetch_readme(owner, repo)
+        .fetch_list_markdown(&list_url)
         .await
         .map_err(|e| format!("Failed to fetch README: {}", e))?;
 
@@ -80,59 +99,668 @@ pub async fn fetch_awesome_list_with_categories(
     Ok(repos)
 }
 
+#[tauri::command]
+pub async fn fetch_awesome_list_entries(
+    url: String,
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AwesomeEntry>, String> {
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
+
+    // Get the token from state
+    let token = state.github_token.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
+
+    // Fetch README
+    let readme = client
+        .fetch_list_markdown(&list_url)
+        .await
+        .map_err(|e| format!("Failed to fetch README: {}", e))?;
+
+    // Parse the list into structured entries
+    let gitea_hosts = state.gitea_hosts.lock().unwrap().clone();
+    Ok(parse_awesome_list(&readme, &gitea_hosts))
+}
+
+#[tauri::command]
+pub async fn fetch_awesome_list_tree(
+    url: String,
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ListSection>, String> {
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
+
+    // Get the token from state
+    let token = state.github_token.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
+
+    // Fetch README
+    let readme = client
+        .fetch_list_markdown(&list_url)
+        .await
+        .map_err(|e| format!("Failed to fetch README: {}", e))?;
+
+    // Group the entries by the list's heading structure
+    let gitea_hosts = state.gitea_hosts.lock().unwrap().clone();
+    Ok(parse_awesome_list_tree(&readme, &gitea_hosts))
+}
+
+#[tauri::command]
+pub async fn fetch_local_awesome_list(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<AwesomeEntry>, String> {
+    // Read the README from a Markdown file or a local working copy
+    let readme = read_local_readme(Path::new(&path))
+        .map_err(|e| format!("Failed to read local list: {}", e))?;
+
+    // Parse the list into structured entries
+    let gitea_hosts = state.gitea_hosts.lock().unwrap().clone();
+    Ok(parse_awesome_list(&readme, &gitea_hosts))
+}
+
+/// Lint an awesome list README. Entries linking to repositories archived in the
+/// stored scan `scan_id` (or the most recent backend scan) are flagged too.
+#[tauri::command]
+pub async fn lint_awesome_list_url(
+    url: String,
+    scan_id: Option<String>,
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<LintDiagnostic>, String> {
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
+
+    // Get the token from state
+    let token = state.github_token.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
+
+    // Fetch README
+    let readme = client
+        .fetch_list_markdown(&list_url)
+        .await
+        .map_err(|e| format!("Failed to fetch README: {}", e))?;
+
+    let archived = archived_repositories(scan_id, &state)?;
+    let gitea_hosts = state.gitea_hosts.lock().unwrap().clone();
+    Ok(lint_awesome_list(&readme, &gitea_hosts, &archived))
+}
+
+/// Lint a README from a local Markdown file or working copy
+#[tauri::command]
+pub async fn lint_local_awesome_list(
+    path: String,
+    scan_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<LintDiagnostic>, String> {
+    let readme = read_local_readme(Path::new(&path))
+        .map_err(|e| format!("Failed to read local list: {}", e))?;
+
+    let archived = archived_repositories(scan_id, &state)?;
+    let gitea_hosts = state.gitea_hosts.lock().unwrap().clone();
+    Ok(lint_awesome_list(&readme, &gitea_hosts, &archived))
+}
+
+/// Check every URL in a list's README, plus the homepages of the repositories in the
+/// stored scan `scan_id` (or the most recent backend scan), for dead links
+#[tauri::command]
+pub async fn check_list_links(
+    url: String,
+    scan_id: Option<String>,
+    concurrency: Option<usize>,
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<LinkCheckResult>, String> {
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
+
+    // Get the token from state
+    let token = state.github_token.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
+
+    // Fetch README
+    let readme = client
+        .fetch_list_markdown(&list_url)
+        .await
+        .map_err(|e| format!("Failed to fetch README: {}", e))?;
+
+    let repositories = scan_or_last(scan_id, &state)?
+        .map(|scan| scan.repositories)
+        .unwrap_or_default();
+    let targets = collect_link_targets(&readme, &repositories);
+
+    Ok(check_links(targets, concurrency.unwrap_or(DEFAULT_LINK_CONCURRENCY), HOST_DELAY).await)
+}
+
+/// The stored scan with `scan_id`, or the most recent backend scan when no ID is given
+fn scan_or_last(scan_id: Option<String>, state: &AppState) -> Result<Option<ScanResult>, String> {
+    match scan_id {
+        Some(scan_id) => state
+            .history
+            .load_scan(&scan_id)
+            .map_err(|e| format!("Failed to load scan: {}", e))?
+            .map(Some)
+            .ok_or_else(|| format!("No stored scan with ID {}", scan_id)),
+        None => Ok(state.last_scan.lock().unwrap().clone()),
+    }
+}
+
+/// Lowercase `owner/repo` links of the archived repositories in a stored scan, or in
+/// the most recent backend scan when no ID is given (empty if there is none)
+fn archived_repositories(scan_id: Option<String>, state: &AppState) -> Result<HashSet<String>, String> {
+    Ok(scan_or_last(scan_id, state)?
+        .map(|scan| scan.repositories)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| r.archived)
+        .map(|r| r.linked_full_name.unwrap_or(r.full_name).to_lowercase())
+        .collect())
+}
+
+#[tauri::command]
+pub async fn crawl_awesome_list(
+    url: String,
+    max_depth: Option<u32>,
+    max_repos: Option<usize>,
+    check_topics: Option<bool>,
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<CrawledEntry>, String> {
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
+
+    // Get the token and Gitea hosts from state
+    let token = state.github_token.lock().unwrap().clone();
+    let gitea_hosts = state.gitea_hosts.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
+
+    let defaults = CrawlOptions::default();
+    let options = CrawlOptions {
+        max_depth: max_depth.unwrap_or(defaults.max_depth),
+        max_repos: max_repos.unwrap_or(defaults.max_repos),
+        check_topics: check_topics.unwrap_or(defaults.check_topics),
+    };
+
+    // Crawl the list and any nested lists within the limits
+    let entries = crawl_awesome_lists(&client, &list_url, &options, &gitea_hosts)
+        .await
+        .map_err(|e| format!("Failed to crawl awesome list: {}", e))?;
+
+    Ok(entries)
+}
+
 #[tauri::command]
 pub async fn fetch_repo_info(
     owner: String,
     repo: String,
+    force_refresh: Option<bool>,
+    include_releases: Option<bool>,
+    include_activity: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<Repository, String> {
     // Get the token from state
     let token = state.github_token.lock().unwrap().clone();
 
     // Create GitHub client
-    let client = GitHubClient::new(token);
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
 
     // Fetch repository info
-    let repository = client
+    let mut repository = client
         .fetch_repository(&owner, &repo)
         .await
         .map_err(|e| format!("Failed to fetch repository: {}", e))?;
 
+    let enrich = EnrichOptions {
+        releases: include_releases.unwrap_or(false),
+        activity: include_activity.unwrap_or(false),
+    };
+    enrich_repository(&client, &mut repository, enrich).await;
+
     Ok(repository)
 }
 
 #[tauri::command]
-pub async fn fetch_added_dates(
-    awesome_list_url: String,
-    repo_links: Vec<(String, String)>,
+pub async fn fetch_repos_info(
+    repos: Vec<(String, String)>,
+    force_refresh: Option<bool>,
     state: State<'_, AppState>,
-) -> Result<HashMap<String, String>, String> {
-    // Parse the awesome list URL to extract owner and repo
-    let url_parts: Vec<&str> = awesome_list_url
-        .trim_end_matches('/')
-        .split('/')
+) -> Result<Vec<RepositoryFetchResult>, String> {
+    // Get the token from state
+    let token = state.github_token.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
+
+    // Fetch all repositories, in GraphQL batches when a token is set
+    Ok(client.fetch_repositories_bulk(&repos).await)
+}
+
+#[tauri::command]
+pub async fn fetch_entry_info(
+    entry: AwesomeEntry,
+    force_refresh: Option<bool>,
+    include_releases: Option<bool>,
+    include_activity: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Repository, String> {
+    if entry.forge == Forge::GitHub {
+        return fetch_repo_info(entry.owner, entry.repo, force_refresh, include_releases, include_activity, state).await;
+    }
+
+    // Fetch repository info from the entry's own forge
+    let repository = ForgeClient::new()
+        .fetch_repository(entry.forge, &entry.host, &entry.owner, &entry.repo)
+        .await
+        .map_err(|e| format!("Failed to fetch repository: {}", e))?;
+
+    Ok(repository)
+}
+
+/// Score the maintenance health of GitHub repositories from 0 to 100, with the
+/// per-signal breakdown. Each repository costs six requests, so scores are fetched
+/// on demand rather than as part of a scan.
+#[tauri::command]
+pub async fn score_repository_health(
+    repos: Vec<(String, String)>,
+    concurrency: Option<usize>,
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<HealthScoreResult>, String> {
+    // Get the token from state
+    let token = state.github_token.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
+
+    Ok(score_repositories(client, repos, concurrency.unwrap_or(DEFAULT_CONCURRENCY)).await)
+}
+
+/// Scan a whole list in the backend.
+///
+/// Emits `scan-started` (`ScanStarted`), `scan-progress` (`ScanProgress`) and
+/// `scan-repository` (`RepositoryFetchResult`) events as the scan runs. Completed
+/// repositories are checkpointed to the app data directory, so an interrupted or
+/// cancelled scan of the same list URL resumes where it stopped unless `resume`
+/// is false. The result is also kept in state so a reloaded webview can pick it
+/// up with `get_last_scan_result`. With `include_releases` the latest release and
+/// release count of each GitHub repository are fetched too, at one extra request each;
+/// `include_activity` adds contributor, commit activity and language statistics.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn scan_awesome_list(
+    url: String,
+    concurrency: Option<usize>,
+    scan_id: Option<String>,
+    resume: Option<bool>,
+    force_refresh: Option<bool>,
+    include_releases: Option<bool>,
+    include_activity: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ScanResult, String> {
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&url).map_err(|e| e.to_string())?;
+
+    // Get the token and Gitea hosts from state
+    let token = state.github_token.lock().unwrap().clone();
+    let gitea_hosts = state.gitea_hosts.lock().unwrap().clone();
+
+    let checkpoint_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?
+        .join("scans");
+
+    // Register the scan before any request so it can be cancelled from the start
+    let scan_id = scan_id.unwrap_or_else(|| format!("scan-{}", chrono::Utc::now().timestamp_millis()));
+    let cancelled = Arc::new(AtomicBool::new(false));
+    state
+        .running_scans
+        .lock()
+        .unwrap()
+        .insert(scan_id.clone(), Arc::clone(&cancelled));
+
+    // Create GitHub client; cancelling also cuts short its rate limit waits
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false))
+        .with_cancel(Arc::clone(&cancelled));
+
+    // Fetch README
+    let readme = match client.fetch_list_markdown(&list_url).await {
+        Ok(readme) => readme,
+        Err(e) => {
+            state.running_scans.lock().unwrap().remove(&scan_id);
+            return Err(format!("Failed to fetch README: {}", e));
+        }
+    };
+
+    let entries = parse_awesome_list(&readme, &gitea_hosts);
+
+    let options = EnrichOptions {
+        releases: include_releases.unwrap_or(false),
+        activity: include_activity.unwrap_or(false),
+    };
+
+    // Pick up the repositories an earlier run of this list already fetched, dropping
+    // any the list no longer links to. A run with other enrich options fetched other
+    // data for them, so it can't be resumed.
+    let mut checkpoint = if resume.unwrap_or(true) {
+        load_checkpoint(&checkpoint_dir, &url)
+            .filter(|c| c.options == options)
+            .unwrap_or_default()
+    } else {
+        ScanCheckpoint::default()
+    };
+    checkpoint.scan_id = scan_id.clone();
+    checkpoint.list_url = url.clone();
+    checkpoint.options = options;
+    if checkpoint.started_at.is_empty() {
+        checkpoint.started_at = chrono::Utc::now().to_rfc3339();
+    }
+
+    let entry_keys: HashSet<String> = entries.iter().map(entry_key).collect();
+    checkpoint.completed.retain(|c| entry_keys.contains(&entry_key(&c.entry)));
+
+    let mut results: HashMap<String, RepositoryFetchResult> = checkpoint
+        .completed
+        .iter()
+        .map(|c| {
+            let result = RepositoryFetchResult::from_result(&c.entry.host, &c.entry.owner, &c.entry.repo, Ok(c.repository.clone()));
+            (entry_key(&c.entry), result)
+        })
+        .collect();
+    let pending: Vec<AwesomeEntry> = entries
+        .iter()
+        .filter(|e| !results.contains_key(&entry_key(e)))
+        .cloned()
         .collect();
 
-    if url_parts.len() < 2 {
-        return Err("Invalid GitHub URL".to_string());
+    let total = entries.len() as u32;
+    let _ = app.emit(
+        "scan-started",
+        ScanStarted {
+            scan_id: scan_id.clone(),
+            list_url: url.clone(),
+            total,
+            resumed: results.len() as u32,
+        },
+    );
+    // Resumed repositories are reported up front, so the frontend's table starts complete
+    for result in results.values() {
+        let _ = app.emit("scan-repository", result.clone());
+    }
+
+    // Fetch the remaining repositories, streaming progress to the frontend
+    let mut current = results.len() as u32;
+    let mut since_checkpoint = 0;
+    let fetched = scan_entries(
+        client,
+        pending,
+        concurrency.unwrap_or(DEFAULT_CONCURRENCY),
+        options,
+        Arc::clone(&cancelled),
+        |entry, repo_result| {
+            current += 1;
+            let progress = ScanProgress {
+                scan_id: scan_id.clone(),
+                total,
+                current,
+                current_repo: format!("{}/{}", entry.owner, entry.repo),
+            };
+            let _ = app.emit("scan-progress", progress);
+            let _ = app.emit("scan-repository", repo_result.clone());
+
+            if let Some(repository) = &repo_result.repository {
+                checkpoint.completed.push(CompletedEntry {
+                    entry: entry.clone(),
+                    repository: repository.clone(),
+                });
+                since_checkpoint += 1;
+                if since_checkpoint >= CHECKPOINT_INTERVAL {
+                    since_checkpoint = 0;
+                    let _ = save_checkpoint(&checkpoint_dir, &checkpoint);
+                }
+            }
+        },
+    )
+    .await;
+    results.extend(fetched);
+
+    state.running_scans.lock().unwrap().remove(&scan_id);
+
+    let was_cancelled = cancelled.load(Ordering::SeqCst);
+    if was_cancelled {
+        save_checkpoint(&checkpoint_dir, &checkpoint)
+            .map_err(|e| format!("Failed to save scan checkpoint: {}", e))?;
+    } else {
+        delete_checkpoint(&checkpoint_dir, &url);
+    }
+
+    let result = build_scan_result(&scan_id, &url, &entries, results, was_cancelled);
+    if let Err(e) = state.history.save_scan(&result) {
+        eprintln!("Failed to save scan {} to history: {}", scan_id, e);
+    }
+    *state.last_scan.lock().unwrap() = Some(result.clone());
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn cancel_scan(
+    scan_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let running_scans = state.running_scans.lock().unwrap();
+    let cancelled = running_scans
+        .get(&scan_id)
+        .ok_or_else(|| format!("No running scan with ID {}", scan_id))?;
+    cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// The checkpoint `scan_awesome_list` would resume for a list with these options, if any
+#[tauri::command]
+pub async fn get_scan_checkpoint(
+    url: String,
+    include_releases: Option<bool>,
+    include_activity: Option<bool>,
+    app: AppHandle,
+) -> Result<Option<ScanCheckpointSummary>, String> {
+    let checkpoint_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?
+        .join("scans");
+    let options = EnrichOptions {
+        releases: include_releases.unwrap_or(false),
+        activity: include_activity.unwrap_or(false),
+    };
+
+    Ok(load_checkpoint(&checkpoint_dir, &url)
+        .filter(|c| c.options == options && !c.completed.is_empty())
+        .map(|c| ScanCheckpointSummary {
+            list_url: c.list_url,
+            started_at: c.started_at,
+            completed: c.completed.len() as u32,
+        }))
+}
+
+#[tauri::command]
+pub async fn get_last_scan_result(
+    state: State<'_, AppState>,
+) -> Result<Option<ScanResult>, String> {
+    Ok(state.last_scan.lock().unwrap().clone())
+}
+
+/// Stored scans, newest first, optionally only those of one list
+#[tauri::command]
+pub async fn list_scans(
+    list_url: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ScanSummary>, String> {
+    state
+        .history
+        .list_scans(list_url.as_deref())
+        .map_err(|e| format!("Failed to list scans: {}", e))
+}
+
+#[tauri::command]
+pub async fn load_scan(
+    scan_id: String,
+    state: State<'_, AppState>,
+) -> Result<ScanResult, String> {
+    state
+        .history
+        .load_scan(&scan_id)
+        .map_err(|e| format!("Failed to load scan: {}", e))?
+        .ok_or_else(|| format!("No stored scan with ID {}", scan_id))
+}
+
+#[tauri::command]
+pub async fn delete_scan(
+    scan_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let deleted = state
+        .history
+        .delete_scan(&scan_id)
+        .map_err(|e| format!("Failed to delete scan: {}", e))?;
+    if !deleted {
+        return Err(format!("No stored scan with ID {}", scan_id));
+    }
+    Ok(())
+}
+
+/// Delete every stored scan taken before an RFC 3339 timestamp, returning how many were removed
+#[tauri::command]
+pub async fn delete_scans_before(
+    before: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    // Scan timestamps are stored in UTC, so compare against the same representation
+    let before = chrono::DateTime::parse_from_rfc3339(&before)
+        .map_err(|e| format!("Invalid timestamp {}: {}", before, e))?
+        .with_timezone(&chrono::Utc)
+        .to_rfc3339();
+
+    state
+        .history
+        .delete_scans_before(&before)
+        .map_err(|e| format!("Failed to delete scans: {}", e))
+}
+
+/// Links in a scanned list that should be updated or removed. Uses the stored scan
+/// with `scan_id`, or the most recent backend scan when none is given.
+#[tauri::command]
+pub async fn list_maintenance_report(
+    scan_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<MaintenanceReport, String> {
+    let scan = scan_or_last(scan_id, &state)?.ok_or_else(|| String::from("No scan has been run yet"))?;
+
+    Ok(build_maintenance_report(&scan))
+}
+
+/// Per-repository star, fork and issue deltas between two scans, biggest star gains first
+#[tauri::command]
+pub async fn compare_scans(
+    from_scan_id: String,
+    to_scan_id: String,
+    state: State<'_, AppState>,
+) -> Result<ScanComparison, String> {
+    let load = |scan_id: &str| {
+        let metrics = state
+            .history
+            .scan_metrics(scan_id)
+            .map_err(|e| format!("Failed to load scan metrics: {}", e))?;
+        if metrics.is_empty() {
+            return Err(format!("No stored metrics for scan {}", scan_id));
+        }
+        Ok(metrics)
+    };
+    let from = load(&from_scan_id)?;
+    let to = load(&to_scan_id)?;
+
+    Ok(compare_metrics(&from_scan_id, &from, &to_scan_id, &to))
+}
+
+/// Metric time series across every stored scan of a list, optionally limited to some repositories
+#[tauri::command]
+pub async fn list_repository_trends(
+    list_url: String,
+    full_names: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RepositoryTrend>, String> {
+    let mut metrics = state
+        .history
+        .list_metrics(&list_url)
+        .map_err(|e| format!("Failed to load scan metrics: {}", e))?;
+
+    if let Some(full_names) = full_names {
+        let wanted: Vec<String> = full_names.iter().map(|n| n.to_lowercase()).collect();
+        metrics.retain(|(full_name, _)| wanted.contains(&full_name.to_lowercase()));
     }
 
-    let owner = url_parts[url_parts.len() - 2];
-    let repo = url_parts[url_parts.len() - 1];
+    Ok(build_trends(metrics))
+}
+
+/// Find when each repository was added to a list from the list file's full history.
+///
+/// The repositories each commit added are cached in the app data directory, so later
+/// runs only inspect commits made since the previous one.
+#[tauri::command]
+pub async fn fetch_added_dates(
+    awesome_list_url: String,
+    repo_links: Vec<(String, String)>,
+    force_refresh: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, String>, String> {
+    // Parse the GitHub URL to extract owner, repo, ref and path
+    let list_url = parse_github_url(&awesome_list_url).map_err(|e| e.to_string())?;
 
     // Get the token from state
     let token = state.github_token.lock().unwrap().clone();
 
     // Create GitHub client
-    let client = GitHubClient::new(token);
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit))
+        .with_cache(Arc::clone(&state.http_cache), force_refresh.unwrap_or(false));
 
-    // Fetch commit history
+    let additions_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?
+        .join("added-dates");
+    let mut additions = load_additions(&additions_dir, &list_url);
+
+    // Fetch commit history, keeping whatever was inspected even if it stops part way
     let dates = client
-        .fetch_commit_history_for_repos(owner, repo, &repo_links)
-        .await
-        .map_err(|e| format!("Failed to fetch commit history: {}", e))?;
+        .fetch_commit_history_for_repos(&list_url, &repo_links, &mut additions)
+        .await;
+    save_additions(&additions_dir, &list_url, &additions)
+        .map_err(|e| format!("Failed to save added dates cache: {}", e))?;
 
-    Ok(dates)
+    dates.map_err(|e| format!("Failed to fetch commit history: {}", e))
 }
 
 #[tauri::command]
@@ -146,41 +774,125 @@ pub async fn set_github_token(
     } else {
         Some(token)
     };
+
+    // Quota is per token, so anything recorded belongs to the old one
+    state.rate_limit.clear();
     Ok(())
 }
 
 #[tauri::command]
+pub async fn set_gitea_hosts(
+    hosts: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut gitea_hosts = state.gitea_hosts.lock().unwrap();
+    *gitea_hosts = hosts
+        .into_iter()
+        .map(|h| h.trim().trim_end_matches('/').to_lowercase())
+        .filter(|h| !h.is_empty())
+        .collect();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_http_cache(
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .http_cache
+        .clear()
+        .map_err(|e| format!("Failed to clear cache: {}", e))
+}
+
+/// Report the remaining GitHub quota, and how long a scan needing `pending_requests`
+/// REST calls would wait for it to reset
+#[tauri::command]
+pub async fn github_rate_limit_status(
+    pending_requests: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<RateLimitStatus, String> {
+    // Get the token from state
+    let token = state.github_token.lock().unwrap().clone();
+
+    // Create GitHub client
+    let client = GitHubClient::new(token, Arc::clone(&state.rate_limit));
+
+    let resources = client
+        .fetch_rate_limit()
+        .await
+        .map_err(|e| format!("Failed to fetch rate limit: {}", e))?;
+
+    let scan_wait_seconds = pending_requests.and_then(|requests| {
+        let core = resources.iter().find(|r| r.resource == "core")?;
+        Some(quota_wait_seconds(core, requests, unix_now()))
+    });
+
+    Ok(RateLimitStatus {
+        authenticated: client.is_authenticated(),
+        resources,
+        scan_wait_seconds,
+    })
+}
+
+/// Export repositories to `path`. JSON and NDJSON exports carry each repository's
+/// category; SQLite exports write a normalized database. CSV exports write `columns` in the given order
+/// (every column by default), separated by `delimiter` (a comma by default), with
+/// topics joined by `topic_separator` and an optional byte order mark. Markdown
+/// exports regenerate an awesome list titled `title`, ordered by `sort` within each
+/// category, with optional star and license `badges`. HTML exports write a
+/// self-contained report titled `title`, stamped with `scanned_at`, or with the
+/// time it was generated when the scan time isn't known.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn export_repositories(
     repositories: Vec<Repository>,
     categories: HashMap<String, String>,
     format: String,
     path: String,
+    columns: Option<Vec<ExportColumn>>,
+    delimiter: Option<char>,
+    topic_separator: Option<String>,
+    bom: Option<bool>,
+    title: Option<String>,
+    sort: Option<ExportSort>,
+    badges: Option<bool>,
+    scanned_at: Option<String>,
 ) -> Result<(), String> {
     use std::fs;
 
     let content = match format.as_str() {
-        "json" => serde_json::to_string_pretty(&repositories)
-            .map_err(|e| format!("Failed to serialize JSON: {}", e))?,
+        "json" => write_json(&repositories, &categories).map_err(|e| e.to_string())?,
+        "ndjson" => write_ndjson(&repositories, &categories).map_err(|e| e.to_string())?,
+        "sqlite" => {
+            return write_sqlite(&repositories, &categories, Path::new(&path))
+                .map_err(|e| format!("Failed to export SQLite database: {}", e));
+        }
         "csv" => {
-            let mut csv = String::from("Name,Full Name,Description,Category,Stars,Forks,Language,License,Updated At,Added to List,URL\n");
-            for repo in repositories {
-                let category = categories.get(&repo.full_name).map(|s| s.as_str()).unwrap_or("");
-                csv.push_str(&format!(
-                    "\"{}\",\"{}\",\"{}\",\"{}\",{},{},\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
-                    repo.name,
-                    repo.full_name,
-                    repo.description.as_deref().unwrap_or(""),
-                    category,
-                    repo.stargazers_count,
-                    repo.forks_count,
-                    repo.language.as_deref().unwrap_or(""),
-                    repo.license.as_ref().map(|l| l.name.as_str()).unwrap_or(""),
-                    repo.updated_at,
-                    repo.added_to_list_at.as_deref().unwrap_or(""),
-                    repo.html_url
-                ));
-            }
-            csv
+            let defaults = CsvOptions::default();
+            let delimiter = check_delimiter(delimiter.unwrap_or(defaults.delimiter)).map_err(|e| e.to_string())?;
+            let options = CsvOptions {
+                columns: columns.filter(|c| !c.is_empty()).unwrap_or(defaults.columns),
+                delimiter,
+                topic_separator: topic_separator.unwrap_or(defaults.topic_separator),
+                bom: bom.unwrap_or(defaults.bom),
+            };
+            write_csv(&repositories, &categories, &options)
+        }
+        "markdown" => {
+            let defaults = MarkdownOptions::default();
+            let options = MarkdownOptions {
+                title: title.unwrap_or(defaults.title),
+                sort: sort.unwrap_or(defaults.sort),
+                badges: badges.unwrap_or(defaults.badges),
+            };
+            write_markdown(&repositories, &categories, &options)
+        }
+        "html" => {
+            let options = HtmlOptions {
+                title: title.unwrap_or_else(|| String::from("Awesome List Report")),
+                scanned_at,
+            };
+            write_html(&repositories, &categories, &options)
         }
         _ => return Err("Unsupported format".to_string()),
     };
@@ -190,3 +902,40 @@ pub async fn export_repositories(
 
     Ok(())
 }
+
+/// Read a JSON, NDJSON or CSV file written by `export_repositories` back into
+/// repositories and their categories. `format` defaults to the file extension; CSV
+/// files are read with `delimiter` and `topic_separator` as exported. Records that
+/// fail validation are reported per row instead of failing the whole import.
+#[tauri::command]
+pub async fn import_repositories(
+    path: String,
+    format: Option<String>,
+    delimiter: Option<char>,
+    topic_separator: Option<String>,
+) -> Result<ImportedRepositories, String> {
+    let format = format
+        .or_else(|| {
+            Path::new(&path)
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_lowercase())
+        })
+        .unwrap_or_default();
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    match format.as_str() {
+        "json" => import_json(&content).map_err(|e| e.to_string()),
+        "ndjson" | "jsonl" => Ok(import_ndjson(&content)),
+        "csv" => {
+            let defaults = CsvOptions::default();
+            let delimiter = check_delimiter(delimiter.unwrap_or(defaults.delimiter)).map_err(|e| e.to_string())?;
+            import_csv(
+                &content,
+                delimiter,
+                &topic_separator.unwrap_or(defaults.topic_separator),
+            )
+            .map_err(|e| e.to_string())
+        }
+        _ => Err("Unsupported format".to_string()),
+    }
+}
@@ -2,9 +2,14 @@ mod commands;
 mod models;
 mod services;
 
-use commands::github::{AppState, fetch_awesome_list, fetch_awesome_list_with_categories, fetch_repo_info, fetch_added_dates, set_github_token, export_repositories};
+use commands::github::{AppState, fetch_awesome_list, fetch_awesome_list_with_categories, fetch_awesome_list_entries, fetch_awesome_list_tree, fetch_local_awesome_list, lint_awesome_list_url, lint_local_awesome_list, check_list_links, crawl_awesome_list, fetch_repo_info, fetch_repos_info, fetch_entry_info, score_repository_health, scan_awesome_list, cancel_scan, get_scan_checkpoint, get_last_scan_result, list_scans, load_scan, delete_scan, delete_scans_before, compare_scans, list_repository_trends, list_maintenance_report, fetch_added_dates, set_github_token, set_gitea_hosts, clear_http_cache, github_rate_limit_status, export_repositories, import_repositories};
 use commands::gitlab::{GitLabState, gitlab_test_connection, gitlab_set_credentials, gitlab_fetch_merge_requests, gitlab_fetch_issues, gitlab_fetch_pipelines, gitlab_fetch_pipeline_jobs, gitlab_approve_mr, gitlab_update_issue, gitlab_retry_pipeline};
-use std::sync::Mutex;
+use services::history::ScanHistory;
+use services::http_cache::HttpCache;
+use services::rate_limit::RateLimitTracker;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -15,18 +20,59 @@ pub fn run() {
         .plugin(tauri_plugin_notification::init())
         .manage(AppState {
             github_token: Mutex::new(None),
+            gitea_hosts: Mutex::new(Vec::new()),
+            last_scan: Mutex::new(None),
+            running_scans: Mutex::new(HashMap::new()),
+            rate_limit: Arc::new(RateLimitTracker::default()),
+            http_cache: Arc::new(HttpCache::default()),
+            history: ScanHistory::default(),
         })
         .manage(GitLabState {
             gitlab_url: Mutex::new(None),
             gitlab_token: Mutex::new(None),
         })
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            let state = app.state::<AppState>();
+            state.http_cache.set_dir(data_dir.join("http-cache"));
+            // Without the database only the history commands fail; the rest of the app still works
+            if let Err(e) = state.history.open(&data_dir.join("history.sqlite3")) {
+                eprintln!("Failed to open scan history: {:#}", e);
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             fetch_awesome_list,
             fetch_awesome_list_with_categories,
+            fetch_awesome_list_entries,
+            fetch_awesome_list_tree,
+            fetch_local_awesome_list,
+            lint_awesome_list_url,
+            lint_local_awesome_list,
+            check_list_links,
+            crawl_awesome_list,
             fetch_repo_info,
+            fetch_repos_info,
+            fetch_entry_info,
+            score_repository_health,
+            scan_awesome_list,
+            cancel_scan,
+            get_scan_checkpoint,
+            get_last_scan_result,
+            list_scans,
+            load_scan,
+            delete_scan,
+            delete_scans_before,
+            compare_scans,
+            list_repository_trends,
+            list_maintenance_report,
             fetch_added_dates,
             set_github_token,
+            set_gitea_hosts,
+            clear_http_cache,
+            github_rate_limit_status,
             export_repositories,
+            import_repositories,
             gitlab_test_connection,
             gitlab_set_credentials,
             gitlab_fetch_merge_requests,
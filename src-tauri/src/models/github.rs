@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
@@ -16,7 +17,73 @@ pub struct Repository {
     pub topics: Vec<String>,
     pub updated_at: String,
     pub created_at: String,
+    /// Last push to any branch; only GitHub reports it
+    #[serde(default)]
+    pub pushed_at: Option<String>,
     pub added_to_list_at: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+    /// Disabled by GitHub staff; the repository can no longer be used
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub is_template: bool,
+    /// The `owner/repo` the list links to, when that link redirects to a different `full_name`
+    #[serde(default)]
+    pub linked_full_name: Option<String>,
+    /// Most recently published release; only filled in when releases were requested
+    #[serde(default)]
+    pub latest_release: Option<Release>,
+    /// Number of published releases; `None` when releases weren't fetched
+    #[serde(default)]
+    pub release_count: Option<u32>,
+    /// Contributor, commit and language statistics; only filled in when requested
+    #[serde(default)]
+    pub activity: Option<RepositoryActivity>,
+}
+
+impl Repository {
+    /// Key of the repository in `ScanResult::categories`
+    pub fn category_key(&self) -> String {
+        let url = self.html_url.trim_end_matches('/');
+        url.split_once("://").map_or(url, |(_, rest)| rest).to_string()
+    }
+}
+
+#[cfg(test)]
+impl Repository {
+    /// A GitHub repository with only its name set, for tests to override the
+    /// fields they care about with `..Repository::base(full_name)`
+    pub fn base(full_name: &str) -> Self {
+        Repository {
+            id: 1,
+            name: full_name.rsplit('/').next().unwrap_or(full_name).to_string(),
+            full_name: full_name.to_string(),
+            description: None,
+            html_url: format!("https://github.com/{}", full_name),
+            homepage: None,
+            stargazers_count: 0,
+            forks_count: 0,
+            open_issues_count: 0,
+            language: None,
+            license: None,
+            topics: Vec::new(),
+            updated_at: String::from("2024-01-01T00:00:00Z"),
+            created_at: String::from("2020-01-01T00:00:00Z"),
+            pushed_at: None,
+            added_to_list_at: None,
+            archived: false,
+            disabled: false,
+            fork: false,
+            is_template: false,
+            linked_full_name: None,
+            latest_release: None,
+            release_count: None,
+            activity: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,9 +94,374 @@ pub struct License {
     pub url: Option<String>,
 }
 
+/// Maintainership statistics from GitHub's stats and languages endpoints. Statistics
+/// GitHub was still computing when asked are `None`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepositoryActivity {
+    /// Contributors with commits on the default branch (GitHub counts at most 100)
+    pub contributor_count: Option<u32>,
+    /// Share of commits by the most active contributor, from 0 to 1; the bus factor
+    pub top_contributor_share: Option<f64>,
+    /// Commits per week over the last 52 weeks, oldest first
+    pub weekly_commits: Option<Vec<u32>>,
+    /// Bytes of code per language, largest first
+    pub languages: Vec<LanguageBytes>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LanguageBytes {
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Release {
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub published_at: Option<String>,
+    pub prerelease: bool,
+}
+
+/// Outcome of fetching one repository as part of a bulk request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryFetchResult {
+    /// Host of the forge the repository was linked on
+    #[serde(default = "github_host")]
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub repository: Option<Repository>,
+    pub error: Option<String>,
+    /// The forge reported the repository as deleted, made private or taken down
+    #[serde(default)]
+    pub unavailable: bool,
+}
+
+impl RepositoryFetchResult {
+    /// Key of the linked repository in `ScanResult::categories`
+    pub fn category_key(&self) -> String {
+        format!("{}/{}/{}", self.host, self.owner, self.repo)
+    }
+}
+
+/// Results stored before forges other than GitHub were scanned
+fn github_host() -> String {
+    String::from("github.com")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ScanProgress {
+    pub scan_id: String,
     pub total: u32,
     pub current: u32,
     pub current_repo: String,
 }
+
+/// Code hosting platform an awesome list entry points at
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+    GitHub,
+    GitLab,
+    Gitea,
+    SourceHut,
+    Bitbucket,
+}
+
+/// A repository link found in an awesome list, with the list's own metadata for it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AwesomeEntry {
+    pub owner: String,
+    pub repo: String,
+    pub forge: Forge,
+    pub host: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub category_path: Vec<String>,
+}
+
+/// An entry reached while crawling an awesome list and the lists it links to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawledEntry {
+    #[serde(flatten)]
+    pub entry: AwesomeEntry,
+    /// `owner/repo` of the list the entry was found in; its section is `entry.category_path`
+    pub parent_list: String,
+    pub depth: u32,
+    /// Whether the crawler descended into this entry as a nested awesome list
+    pub is_list: bool,
+}
+
+/// A heading of an awesome list with the entries listed directly under it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListSection {
+    pub title: String,
+    pub anchor: String,
+    pub depth: u8,
+    pub sections: Vec<ListSection>,
+    pub entries: Vec<AwesomeEntry>,
+}
+
+/// Payload of the `scan-started` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanStarted {
+    pub scan_id: String,
+    pub list_url: String,
+    pub total: u32,
+    /// Repositories restored from a checkpoint instead of being fetched again
+    pub resumed: u32,
+}
+
+/// An interrupted scan that the next scan of its list can resume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCheckpointSummary {
+    pub list_url: String,
+    pub started_at: String,
+    /// Repositories already fetched
+    pub completed: u32,
+}
+
+/// Final dataset of a backend-driven scan of an awesome list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub scan_id: String,
+    pub list_url: String,
+    pub scanned_at: String,
+    /// Whether the scan was stopped early; the remaining entries are checkpointed
+    pub cancelled: bool,
+    pub repositories: Vec<Repository>,
+    /// Category of each repository, keyed by host and path (`github.com/owner/name`) so
+    /// the same name on two forges stays apart. Repositories are keyed by their URL and
+    /// failures by the link in the list.
+    pub categories: HashMap<String, String>,
+    pub failures: Vec<RepositoryFetchResult>,
+}
+
+/// A stored scan as listed in the scan history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSummary {
+    pub scan_id: String,
+    pub list_url: String,
+    pub scanned_at: String,
+    pub cancelled: bool,
+    pub repository_count: u32,
+    pub failure_count: u32,
+}
+
+/// Metrics of one repository as recorded by one scan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricPoint {
+    pub scan_id: String,
+    pub scanned_at: String,
+    pub stargazers_count: u32,
+    pub forks_count: u32,
+    pub open_issues_count: u32,
+    pub archived: bool,
+}
+
+/// Metrics of one repository across every stored scan of a list, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryTrend {
+    pub full_name: String,
+    pub points: Vec<MetricPoint>,
+}
+
+/// Change in one repository's metrics between two scans
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepositoryDelta {
+    pub full_name: String,
+    /// Stars at the later scan
+    pub stargazers_count: u32,
+    pub stars_gained: i64,
+    pub forks_gained: i64,
+    /// Change in open issues (and pull requests); negative when more were closed than opened
+    pub issues_opened: i64,
+    pub newly_archived: bool,
+    /// Not present in the earlier scan, so every count is gained from zero
+    pub is_new: bool,
+}
+
+/// Per-repository deltas between two scans, biggest star gains first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanComparison {
+    pub from_scan_id: String,
+    pub to_scan_id: String,
+    pub from_scanned_at: String,
+    pub to_scanned_at: String,
+    pub deltas: Vec<RepositoryDelta>,
+    /// Repositories in the earlier scan that the later one no longer has
+    pub removed: Vec<String>,
+}
+
+/// Why a list entry needs attention
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MaintenanceIssueKind {
+    Renamed,
+    Transferred,
+    Archived,
+    Disabled,
+    Deleted,
+    Fork,
+}
+
+/// What the list maintainer should do about an entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MaintenanceAction {
+    /// Point the link at the canonical repository
+    Update,
+    Remove,
+    /// Still works, but worth a second look
+    Review,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceIssue {
+    /// `owner/repo` as linked from the list
+    pub linked: String,
+    pub category: Option<String>,
+    pub kind: MaintenanceIssueKind,
+    pub action: MaintenanceAction,
+    pub canonical_full_name: Option<String>,
+    pub canonical_url: Option<String>,
+    pub detail: String,
+}
+
+/// Links in a scanned list that should be updated or removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub scan_id: String,
+    pub list_url: String,
+    pub scanned_at: String,
+    pub issues: Vec<MaintenanceIssue>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+/// A problem found in an awesome list README, positioned at 1-based line and column
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LintDiagnostic {
+    pub rule: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkStatus {
+    Ok,
+    /// Reachable, but only through one or more redirects
+    Redirected,
+    /// The server answered with an error status
+    Broken,
+    /// No answer: DNS, TLS, timeout or too many redirects
+    Error,
+}
+
+/// Outcome of checking one distinct URL from a list or a repository homepage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCheckResult {
+    pub url: String,
+    /// Sections of the README the URL appears in
+    pub sections: Vec<String>,
+    /// 1-based line of the first occurrence in the README; `None` for homepages only
+    pub line: Option<u32>,
+    /// Repositories whose homepage is this URL
+    pub repositories: Vec<String>,
+    pub status: LinkStatus,
+    pub http_status: Option<u16>,
+    /// Every location redirected through, ending at the final URL
+    pub redirects: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Raw activity signals a health score is computed from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthSignals {
+    pub pushed_at: Option<String>,
+    /// Commits on the default branch over the last 90 days
+    pub recent_commits: u32,
+    /// Open and closed counts both include pull requests, as GitHub's issue counts do
+    pub open_issues: u32,
+    pub closed_issues: u32,
+    pub release_count: u32,
+    pub latest_release_at: Option<String>,
+    /// `None` when GitHub refuses to list the contributors of a very large repository
+    pub contributors: Option<u32>,
+    pub archived: bool,
+    /// At least one GitHub Actions workflow is defined
+    pub has_ci: bool,
+}
+
+/// Points one signal contributed to a health score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthComponent {
+    pub signal: String,
+    /// Most points the signal can contribute
+    pub weight: u32,
+    pub points: u32,
+    pub detail: String,
+}
+
+/// A 0–100 maintenance health score with the breakdown it was computed from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthScore {
+    pub full_name: String,
+    pub score: u32,
+    pub components: Vec<HealthComponent>,
+    pub signals: HealthSignals,
+}
+
+/// Outcome of scoring one repository as part of a bulk request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthScoreResult {
+    pub owner: String,
+    pub repo: String,
+    pub health: Option<HealthScore>,
+    pub error: Option<String>,
+}
+
+/// A record of an imported file that couldn't be read back as a repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImportError {
+    /// 1-based line for CSV and NDJSON files, 1-based array position for JSON
+    pub row: u32,
+    pub message: String,
+}
+
+/// Repositories read back from an export, with the records that failed validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportedRepositories {
+    pub repositories: Vec<Repository>,
+    /// Category of each repository, keyed by `full_name`
+    pub categories: HashMap<String, String>,
+    pub errors: Vec<ImportError>,
+}
+
+/// Quota GitHub reported for one rate limit resource
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RateLimitInfo {
+    pub resource: String,
+    pub limit: u32,
+    pub remaining: u32,
+    pub used: u32,
+    /// Unix timestamp (seconds) at which the quota resets
+    pub reset: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitStatus {
+    pub authenticated: bool,
+    pub resources: Vec<RateLimitInfo>,
+    /// Seconds the requested scan would spend waiting for the core quota to reset
+    pub scan_wait_seconds: Option<u64>,
+}
@@ -0,0 +1,99 @@
+use crate::services::github::GitHubListUrl;
+use crate::services::markdown::parse_github_repo_url;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Repositories one commit added to a list file. Commits never change, so once a
+/// commit has been inspected it is never fetched again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitAdditions {
+    pub date: String,
+    /// Lowercase `owner/repo` of every GitHub repository linked on an added line
+    pub repos: Vec<String>,
+}
+
+/// Per-commit additions to one list file, keyed by commit SHA
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdditionsCache {
+    pub commits: HashMap<String, CommitAdditions>,
+}
+
+/// One cache file per list file, since a commit's patch is only inspected for that file
+fn cache_path(dir: &Path, list: &GitHubListUrl) -> PathBuf {
+    let key: String = format!(
+        "{}/{}/{}/{}",
+        list.owner,
+        list.repo,
+        list.git_ref.as_deref().unwrap_or(""),
+        list.markdown_path()
+    )
+    .to_lowercase()
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+    .collect();
+    dir.join(format!("{}.json", key))
+}
+
+pub fn load_additions(dir: &Path, list: &GitHubListUrl) -> AdditionsCache {
+    fs::read_to_string(cache_path(dir, list))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_additions(dir: &Path, list: &GitHubListUrl, cache: &AdditionsCache) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create added dates cache directory")?;
+    let content = serde_json::to_string(cache).context("Failed to serialize added dates cache")?;
+    fs::write(cache_path(dir, list), content).context("Failed to write added dates cache")
+}
+
+/// A URL on a diff line, which may also sit inside a quoted HTML attribute
+static URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"https?://[^\s<>()\[\]'"`]+"#).unwrap());
+
+/// Lowercase `owner/repo` of the GitHub repositories linked on the `+` lines of a unified diff
+pub fn added_repo_links(patch: &str) -> Vec<String> {
+    let mut repos = Vec::new();
+    for line in patch.lines() {
+        let Some(added) = line.strip_prefix('+') else {
+            continue;
+        };
+        if line.starts_with("+++") {
+            continue;
+        }
+        for url in URL_PATTERN.find_iter(added) {
+            if let Some((owner, repo)) = parse_github_repo_url(url.as_str()) {
+                let key = format!("{}/{}", owner, repo).to_lowercase();
+                if !repos.contains(&key) {
+                    repos.push(key);
+                }
+            }
+        }
+    }
+    repos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_added_repo_links() {
+        let patch = "@@ -10,6 +10,8 @@ ## Tools
+ - [Kept](https://github.com/owner/kept) - Unchanged.
+-- [Removed](https://github.com/owner/removed) - Gone.
++- [Serde](https://github.com/serde-rs/serde) - Serialization.
++- [Tokio](https://github.com/Tokio-RS/tokio.git) and [docs](https://tokio.rs).
++<a href=\"https://github.com/serde-rs/serde\">again</a>
++- [Sponsor](https://github.com/sponsors/someone)";
+
+        assert_eq!(
+            added_repo_links(patch),
+            vec![String::from("serde-rs/serde"), String::from("tokio-rs/tokio")]
+        );
+    }
+}
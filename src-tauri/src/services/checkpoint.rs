@@ -0,0 +1,101 @@
+use crate::models::github::{AwesomeEntry, Repository};
+use crate::services::http_cache::fnv1a;
+use crate::services::scanner::EnrichOptions;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Repositories already fetched by an interrupted scan of a list
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    pub scan_id: String,
+    pub list_url: String,
+    pub started_at: String,
+    /// Optional data fetched for the completed repositories; a scan asking for
+    /// different data starts over
+    #[serde(default)]
+    pub options: EnrichOptions,
+    pub completed: Vec<CompletedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletedEntry {
+    pub entry: AwesomeEntry,
+    pub repository: Repository,
+}
+
+/// Checkpoints are keyed by a hash of the normalised list URL, so rescanning the same
+/// list resumes it and URLs differing only in punctuation don't share a file
+fn checkpoint_path(dir: &Path, list_url: &str) -> PathBuf {
+    let key = list_url.trim().trim_end_matches('/').to_lowercase();
+    dir.join(format!("{:016x}.json", fnv1a(&key)))
+}
+
+pub fn load_checkpoint(dir: &Path, list_url: &str) -> Option<ScanCheckpoint> {
+    let content = fs::read_to_string(checkpoint_path(dir, list_url)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save_checkpoint(dir: &Path, checkpoint: &ScanCheckpoint) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create checkpoint directory")?;
+    let content = serde_json::to_string(checkpoint).context("Failed to serialize checkpoint")?;
+
+    // Write beside the checkpoint and rename over it, so a crash mid-write keeps the old one
+    let path = checkpoint_path(dir, &checkpoint.list_url);
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, content).context("Failed to write checkpoint")?;
+    fs::rename(&temp, &path).context("Failed to replace checkpoint")
+}
+
+pub fn delete_checkpoint(dir: &Path, list_url: &str) {
+    let _ = fs::remove_file(checkpoint_path(dir, list_url));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::github::Forge;
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let dir = std::env::temp_dir().join(format!("checkpoint-test-{}", std::process::id()));
+        let list_url = "https://github.com/org/awesome";
+        assert!(load_checkpoint(&dir, list_url).is_none());
+
+        let checkpoint = ScanCheckpoint {
+            scan_id: String::from("scan-1"),
+            list_url: String::from(list_url),
+            started_at: String::from("2024-06-01T00:00:00+00:00"),
+            options: EnrichOptions { releases: true, activity: false },
+            completed: vec![CompletedEntry {
+                entry: AwesomeEntry {
+                    owner: String::from("org"),
+                    repo: String::from("tool"),
+                    forge: Forge::GitHub,
+                    host: String::from("github.com"),
+                    name: String::from("Tool"),
+                    description: None,
+                    category_path: vec![String::from("Tools")],
+                },
+                repository: Repository::base("org/tool"),
+            }],
+        };
+        save_checkpoint(&dir, &checkpoint).unwrap();
+
+        // The same list with a trailing slash or different case shares the checkpoint
+        let loaded = load_checkpoint(&dir, "https://GitHub.com/org/awesome/").unwrap();
+        assert_eq!(loaded.scan_id, "scan-1");
+        assert_eq!(loaded.options, EnrichOptions { releases: true, activity: false });
+        assert_eq!(loaded.completed.len(), 1);
+        assert_eq!(loaded.completed[0].entry.category_path, vec!["Tools"]);
+        assert_eq!(loaded.completed[0].repository.full_name, "org/tool");
+        assert!(load_checkpoint(&dir, "https://github.com/org/other").is_none());
+        assert!(load_checkpoint(&dir, "https://github.com/org_awesome").is_none());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        delete_checkpoint(&dir, list_url);
+        assert!(load_checkpoint(&dir, list_url).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+use crate::models::github::{AwesomeEntry, CrawledEntry, Forge};
+use crate::services::github::{GitHubClient, GitHubListUrl};
+use crate::services::markdown::parse_awesome_list;
+use anyhow::Result;
+use std::collections::{HashSet, VecDeque};
+
+/// A README needs at least this many entries to count as a list of links
+const MIN_LIST_ENTRIES: usize = 10;
+
+/// Share of non-blank README lines that must contain a link for it to count as a list
+const MIN_LINK_LINE_RATIO: f64 = 0.3;
+
+pub struct CrawlOptions {
+    /// How many levels of nested lists to descend into; 0 only scans the root list
+    pub max_depth: u32,
+    /// Stop collecting once this many repositories have been recorded
+    pub max_repos: usize,
+    /// Also fetch repository metadata to check for the `awesome` topic when the name doesn't match
+    pub check_topics: bool,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 1,
+            max_repos: 2000,
+            check_topics: false,
+        }
+    }
+}
+
+/// Crawl an awesome list and the awesome lists it links to, breadth first.
+///
+/// Every repository is recorded once, with the list and section it was first
+/// reached through. Nested lists that fail to load are kept as plain entries.
+pub async fn crawl_awesome_lists(
+    client: &GitHubClient,
+    list: &GitHubListUrl,
+    options: &CrawlOptions,
+    gitea_hosts: &[String],
+) -> Result<Vec<CrawledEntry>> {
+    let mut crawled: Vec<CrawledEntry> = Vec::new();
+    let mut seen_repos = HashSet::new();
+    let mut visited_lists = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    let root = format!("{}/{}", list.owner, list.repo);
+    visited_lists.insert(root.to_lowercase());
+    queue.push_back((root, client.fetch_list_markdown(list).await?, 0));
+
+    while let Some((list, readme, depth)) = queue.pop_front() {
+        for entry in parse_awesome_list(&readme, gitea_hosts) {
+            if crawled.len() >= options.max_repos {
+                return Ok(crawled);
+            }
+
+            let full_name = format!("{}/{}/{}", entry.host, entry.owner, entry.repo).to_lowercase();
+            if !seen_repos.insert(full_name) {
+                continue;
+            }
+
+            let mut is_list = false;
+            if depth < options.max_depth && entry.forge == Forge::GitHub {
+                let list_name = format!("{}/{}", entry.owner, entry.repo);
+                if !visited_lists.contains(&list_name.to_lowercase())
+                    && is_awesome_candidate(client, &entry, options).await
+                {
+                    visited_lists.insert(list_name.to_lowercase());
+                    if let Ok(child_readme) = client.fetch_readme(&entry.owner, &entry.repo).await {
+                        if looks_like_link_list(&child_readme, gitea_hosts) {
+                            is_list = true;
+                            queue.push_back((list_name, child_readme, depth + 1));
+                        }
+                    }
+                }
+            }
+
+            crawled.push(CrawledEntry {
+                entry,
+                parent_list: list.clone(),
+                depth,
+                is_list,
+            });
+        }
+    }
+
+    Ok(crawled)
+}
+
+/// Whether an entry may be a nested list: by name, or optionally by its `awesome` topic
+async fn is_awesome_candidate(client: &GitHubClient, entry: &AwesomeEntry, options: &CrawlOptions) -> bool {
+    if has_awesome_name(entry) {
+        return true;
+    }
+
+    if options.check_topics {
+        if let Ok(repository) = client.fetch_repository(&entry.owner, &entry.repo).await {
+            return repository.topics.iter().any(|t| t == "awesome" || t == "awesome-list");
+        }
+    }
+
+    false
+}
+
+/// Awesome lists are named `awesome-*` by convention, or at least say so in their link text
+fn has_awesome_name(entry: &AwesomeEntry) -> bool {
+    entry.repo.to_lowercase().contains("awesome") || entry.name.to_lowercase().contains("awesome")
+}
+
+/// A README is treated as a list when it has enough entries and most of its lines carry links
+fn looks_like_link_list(markdown: &str, gitea_hosts: &[String]) -> bool {
+    if parse_awesome_list(markdown, gitea_hosts).len() < MIN_LIST_ENTRIES {
+        return false;
+    }
+
+    let lines: Vec<&str> = markdown.lines().filter(|l| !l.trim().is_empty()).collect();
+    let link_lines = lines.iter().filter(|l| l.contains("](") || l.contains("href=")).count();
+
+    !lines.is_empty() && link_lines as f64 / lines.len() as f64 >= MIN_LINK_LINE_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(repo: &str, name: &str) -> AwesomeEntry {
+        AwesomeEntry {
+            owner: String::from("org"),
+            repo: repo.to_string(),
+            forge: Forge::GitHub,
+            host: String::from("github.com"),
+            name: name.to_string(),
+            description: None,
+            category_path: Vec::new(),
+        }
+    }
+
+    fn list(entries: usize, prose_lines: usize) -> String {
+        let mut markdown = String::from("# Awesome Things\n\n");
+        for i in 0..prose_lines {
+            markdown.push_str(&format!("Paragraph {} about the project.\n\n", i));
+        }
+        for i in 0..entries {
+            markdown.push_str(&format!("- [Tool {i}](https://github.com/org/tool-{i}) - Does things.\n"));
+        }
+        markdown
+    }
+
+    #[test]
+    fn test_has_awesome_name() {
+        assert!(has_awesome_name(&entry("awesome-rust", "Rust")));
+        assert!(has_awesome_name(&entry("AwesomeRust", "Rust")));
+        assert!(has_awesome_name(&entry("rust-resources", "Awesome Rust")));
+        assert!(!has_awesome_name(&entry("ripgrep", "ripgrep")));
+    }
+
+    #[test]
+    fn test_looks_like_link_list() {
+        assert!(looks_like_link_list(&list(MIN_LIST_ENTRIES, 0), &[]));
+        // Too few entries, however dense
+        assert!(!looks_like_link_list(&list(MIN_LIST_ENTRIES - 1, 0), &[]));
+        // Enough entries, but mostly prose: 10 link lines out of 41
+        assert!(!looks_like_link_list(&list(MIN_LIST_ENTRIES, 30), &[]));
+        // 10 link lines out of 21 clears the 30% bar
+        assert!(looks_like_link_list(&list(MIN_LIST_ENTRIES, 10), &[]));
+    }
+}
@@ -0,0 +1,1044 @@
+use crate::models::github::Repository;
+use crate::services::markdown::unique_anchor;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Section for repositories the `categories` map doesn't cover
+const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Section the Markdown export moves archived repositories to
+const ARCHIVED: &str = "Archived";
+
+/// A column of a tabular export
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportColumn {
+    Name,
+    FullName,
+    Description,
+    Category,
+    Stars,
+    Forks,
+    OpenIssues,
+    Language,
+    License,
+    Topics,
+    Homepage,
+    CreatedAt,
+    UpdatedAt,
+    AddedToList,
+    LatestRelease,
+    ReleaseDate,
+    Releases,
+    Contributors,
+    Url,
+}
+
+impl ExportColumn {
+    /// Every column, in the default export order
+    pub const ALL: [ExportColumn; 19] = [
+        ExportColumn::Name,
+        ExportColumn::FullName,
+        ExportColumn::Description,
+        ExportColumn::Category,
+        ExportColumn::Stars,
+        ExportColumn::Forks,
+        ExportColumn::OpenIssues,
+        ExportColumn::Language,
+        ExportColumn::License,
+        ExportColumn::Topics,
+        ExportColumn::Homepage,
+        ExportColumn::CreatedAt,
+        ExportColumn::UpdatedAt,
+        ExportColumn::AddedToList,
+        ExportColumn::LatestRelease,
+        ExportColumn::ReleaseDate,
+        ExportColumn::Releases,
+        ExportColumn::Contributors,
+        ExportColumn::Url,
+    ];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            ExportColumn::Name => "Name",
+            ExportColumn::FullName => "Full Name",
+            ExportColumn::Description => "Description",
+            ExportColumn::Category => "Category",
+            ExportColumn::Stars => "Stars",
+            ExportColumn::Forks => "Forks",
+            ExportColumn::OpenIssues => "Open Issues",
+            ExportColumn::Language => "Language",
+            ExportColumn::License => "License",
+            ExportColumn::Topics => "Topics",
+            ExportColumn::Homepage => "Homepage",
+            ExportColumn::CreatedAt => "Created At",
+            ExportColumn::UpdatedAt => "Updated At",
+            ExportColumn::AddedToList => "Added to List",
+            ExportColumn::LatestRelease => "Latest Release",
+            ExportColumn::ReleaseDate => "Release Date",
+            ExportColumn::Releases => "Releases",
+            ExportColumn::Contributors => "Contributors",
+            ExportColumn::Url => "URL",
+        }
+    }
+
+    /// The column a CSV header names, ignoring case and surrounding whitespace
+    pub fn from_header(header: &str) -> Option<Self> {
+        let header = header.trim();
+        Self::ALL.into_iter().find(|c| c.header().eq_ignore_ascii_case(header))
+    }
+
+    /// The cell for one repository; missing values are empty
+    pub fn value(self, repo: &Repository, category: Option<&str>, topic_separator: &str) -> String {
+        let text = |value: Option<&str>| value.unwrap_or_default().to_string();
+        match self {
+            ExportColumn::Name => repo.name.clone(),
+            ExportColumn::FullName => repo.full_name.clone(),
+            ExportColumn::Description => text(repo.description.as_deref()),
+            ExportColumn::Category => text(category),
+            ExportColumn::Stars => repo.stargazers_count.to_string(),
+            ExportColumn::Forks => repo.forks_count.to_string(),
+            ExportColumn::OpenIssues => repo.open_issues_count.to_string(),
+            ExportColumn::Language => text(repo.language.as_deref()),
+            ExportColumn::License => text(repo.license.as_ref().map(|l| l.name.as_str())),
+            ExportColumn::Topics => repo.topics.join(topic_separator),
+            ExportColumn::Homepage => text(repo.homepage.as_deref()),
+            ExportColumn::CreatedAt => repo.created_at.clone(),
+            ExportColumn::UpdatedAt => repo.updated_at.clone(),
+            ExportColumn::AddedToList => text(repo.added_to_list_at.as_deref()),
+            ExportColumn::LatestRelease => text(repo.latest_release.as_ref().map(|r| r.tag_name.as_str())),
+            ExportColumn::ReleaseDate => text(repo.latest_release.as_ref().and_then(|r| r.published_at.as_deref())),
+            ExportColumn::Releases => repo.release_count.map(|c| c.to_string()).unwrap_or_default(),
+            ExportColumn::Contributors => repo
+                .activity
+                .as_ref()
+                .and_then(|a| a.contributor_count)
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            ExportColumn::Url => repo.html_url.clone(),
+        }
+    }
+}
+
+pub struct CsvOptions {
+    /// Columns to write, in order
+    pub columns: Vec<ExportColumn>,
+    pub delimiter: char,
+    /// Joins a repository's topics within the single topics cell
+    pub topic_separator: String,
+    /// Start with a UTF-8 byte order mark, which Excel needs to detect the encoding
+    pub bom: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            columns: ExportColumn::ALL.to_vec(),
+            delimiter: ',',
+            topic_separator: String::from(";"),
+            bom: false,
+        }
+    }
+}
+
+/// Reject delimiters that can't separate fields: a quote or line break would be read
+/// back as part of the quoting or as the end of a record
+pub fn check_delimiter(delimiter: char) -> Result<char> {
+    if matches!(delimiter, '"' | '\r' | '\n') {
+        anyhow::bail!("{:?} can't be used as a CSV delimiter", delimiter);
+    }
+    Ok(delimiter)
+}
+
+/// Write repositories as RFC 4180 CSV: a header row, CRLF line endings, and fields
+/// quoted (with embedded quotes doubled) whenever they contain the delimiter, a
+/// quote or a line break. `categories` is keyed by `full_name`.
+pub fn write_csv(repositories: &[Repository], categories: &HashMap<String, String>, options: &CsvOptions) -> String {
+    let mut csv = String::new();
+    if options.bom {
+        csv.push('\u{feff}');
+    }
+
+    let header: Vec<String> = options.columns.iter().map(|c| c.header().to_string()).collect();
+    push_record(&mut csv, &header, options.delimiter);
+
+    for repo in repositories {
+        let category = categories.get(&repo.full_name).map(String::as_str);
+        let record: Vec<String> = options
+            .columns
+            .iter()
+            .map(|c| c.value(repo, category, &options.topic_separator))
+            .collect();
+        push_record(&mut csv, &record, options.delimiter);
+    }
+
+    csv
+}
+
+fn push_record(csv: &mut String, fields: &[String], delimiter: char) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            csv.push(delimiter);
+        }
+        if field.contains([delimiter, '"', '\r', '\n']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push_str("\r\n");
+}
+
+/// Order of repositories within each exported section
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportSort {
+    /// Most stars first
+    Stars,
+    /// Case-insensitive by name
+    Name,
+    /// Most recently added to the list first, undated repositories last
+    Added,
+}
+
+pub struct MarkdownOptions {
+    pub title: String,
+    pub sort: ExportSort,
+    /// Add static star and license badges with the scanned values
+    pub badges: bool,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            title: String::from("Awesome List"),
+            sort: ExportSort::Stars,
+            badges: false,
+        }
+    }
+}
+
+/// Write repositories back out as an awesome list README: a table of contents, then
+/// one section per category in order of first appearance, with archived repositories
+/// gathered in a final "Archived" section, or in the list's own "Archived" category
+/// when it has one. `categories` is keyed by `full_name`.
+pub fn write_markdown(
+    repositories: &[Repository],
+    categories: &HashMap<String, String>,
+    options: &MarkdownOptions,
+) -> String {
+    let mut sections: Vec<(String, Vec<&Repository>)> = Vec::new();
+    let mut archived: Vec<&Repository> = Vec::new();
+    for repo in repositories {
+        if repo.archived {
+            archived.push(repo);
+            continue;
+        }
+        let category = categories
+            .get(&repo.full_name)
+            .map(String::as_str)
+            .unwrap_or(UNCATEGORIZED);
+        match sections.iter_mut().find(|(title, _)| title == category) {
+            Some((_, repos)) => repos.push(repo),
+            None => sections.push((category.to_string(), vec![repo])),
+        }
+    }
+    if !archived.is_empty() {
+        match sections.iter_mut().find(|(title, _)| title == ARCHIVED) {
+            Some((_, repos)) => repos.extend(archived),
+            None => sections.push((String::from(ARCHIVED), archived)),
+        }
+    }
+    for (_, repos) in &mut sections {
+        sort_repositories(repos, options.sort);
+    }
+
+    let mut anchors = HashMap::new();
+    unique_anchor(&mut anchors, &options.title);
+    unique_anchor(&mut anchors, "Contents");
+
+    let mut markdown = format!("# {}\n\n## Contents\n\n", options.title);
+    let mut body = String::new();
+    for (title, repos) in &sections {
+        markdown.push_str(&format!("- [{}](#{})\n", title, unique_anchor(&mut anchors, title)));
+
+        body.push_str(&format!("\n## {}\n\n", title));
+        for repo in repos {
+            body.push_str(&markdown_entry(repo, options.badges));
+        }
+    }
+
+    markdown.push_str(&body);
+    markdown
+}
+
+fn sort_repositories(repos: &mut [&Repository], sort: ExportSort) {
+    match sort {
+        ExportSort::Stars => repos.sort_by_key(|r| Reverse(r.stargazers_count)),
+        ExportSort::Name => repos.sort_by_key(|r| r.name.to_lowercase()),
+        // RFC 3339 timestamps sort chronologically as strings
+        ExportSort::Added => repos.sort_by_key(|r| Reverse((r.added_to_list_at.is_some(), r.added_to_list_at.clone()))),
+    }
+}
+
+fn markdown_entry(repo: &Repository, badges: bool) -> String {
+    let mut entry = format!("- [{}]({})", repo.name.replace(['[', ']'], ""), repo.html_url);
+
+    if badges {
+        entry.push_str(&format!(
+            " ![Stars](https://img.shields.io/badge/stars-{}-blue)",
+            repo.stargazers_count
+        ));
+        if let Some(license) = &repo.license {
+            // GitHub reports licenses it doesn't recognise as NOASSERTION
+            let name = license
+                .spdx_id
+                .as_deref()
+                .filter(|id| !id.is_empty() && *id != "NOASSERTION")
+                .unwrap_or(&license.name);
+            entry.push_str(&format!(
+                " ![License](https://img.shields.io/badge/license-{}-green)",
+                shields_escape(name)
+            ));
+        }
+    }
+
+    if let Some(description) = repo.description.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        let description = description.split_whitespace().collect::<Vec<_>>().join(" ");
+        entry.push_str(" - ");
+        entry.push_str(&description);
+        // awesome-lint expects every description to end with punctuation
+        if !description.ends_with(['.', '!', '?']) {
+            entry.push('.');
+        }
+    }
+
+    entry.push('\n');
+    entry
+}
+
+/// Escape a static shields.io badge segment: `-` and `_` are separators there and
+/// are doubled, and everything else outside the URL-safe characters is percent-encoded
+fn shields_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '-' => escaped.push_str("--"),
+            '_' => escaped.push_str("__"),
+            c if c.is_ascii_alphanumeric() || c == '.' || c == '~' => escaped.push(c),
+            c => {
+                let mut bytes = [0; 4];
+                for byte in c.encode_utf8(&mut bytes).bytes() {
+                    escaped.push_str(&format!("%{:02X}", byte));
+                }
+            }
+        }
+    }
+    escaped
+}
+
+pub struct HtmlOptions {
+    pub title: String,
+    /// When the repositories were scanned, shown in the report header; without it the
+    /// header shows when the report was generated instead
+    pub scanned_at: Option<String>,
+}
+
+/// Columns of the HTML report table: header, and whether it sorts numerically
+const HTML_COLUMNS: [(&str, bool); 8] = [
+    ("Name", false),
+    ("Description", false),
+    ("Stars", true),
+    ("Forks", true),
+    ("Open Issues", true),
+    ("Language", false),
+    ("License", false),
+    ("Updated", false),
+];
+
+const HTML_STYLE: &str = "
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; margin: 2rem; color: #1f2937; }
+header p { color: #6b7280; }
+.stats { display: flex; flex-wrap: wrap; gap: 1rem; margin: 1rem 0; }
+.stat { background: #f3f4f6; border-radius: 8px; padding: 0.75rem 1rem; }
+.stat strong { display: block; font-size: 1.4rem; }
+.controls { display: flex; gap: 0.5rem; margin: 1rem 0; }
+.controls input, .controls select { padding: 0.4rem; border: 1px solid #d1d5db; border-radius: 6px; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.4rem 0.6rem; border-bottom: 1px solid #e5e7eb; vertical-align: top; }
+th { cursor: pointer; user-select: none; background: #f9fafb; position: sticky; top: 0; }
+th.asc::after { content: ' \u{25B2}'; }
+th.desc::after { content: ' \u{25BC}'; }
+tr.group td { background: #ede9fe; font-weight: bold; }
+tr.archived { color: #9ca3af; }
+td.number { text-align: right; }
+a { color: #2563eb; }
+";
+
+const HTML_SCRIPT: &str = "
+const search = document.getElementById('search');
+const category = document.getElementById('category');
+const groups = Array.from(document.querySelectorAll('tbody'));
+function applyFilter() {
+  const query = search.value.toLowerCase();
+  for (const group of groups) {
+    const visibleGroup = !category.value || group.dataset.category === category.value;
+    let visibleRows = 0;
+    for (const row of group.querySelectorAll('tr.repo')) {
+      const show = visibleGroup && row.dataset.search.includes(query);
+      row.hidden = !show;
+      if (show) visibleRows++;
+    }
+    group.hidden = visibleRows === 0;
+  }
+}
+search.addEventListener('input', applyFilter);
+category.addEventListener('change', applyFilter);
+document.querySelectorAll('th').forEach((th, column) => {
+  th.addEventListener('click', () => {
+    const ascending = !th.classList.contains('asc');
+    document.querySelectorAll('th').forEach(other => other.classList.remove('asc', 'desc'));
+    th.classList.add(ascending ? 'asc' : 'desc');
+    const numeric = th.dataset.numeric === 'true';
+    for (const group of groups) {
+      const rows = Array.from(group.querySelectorAll('tr.repo'));
+      rows.sort((a, b) => {
+        const x = a.children[column].dataset.value;
+        const y = b.children[column].dataset.value;
+        const order = numeric ? Number(x) - Number(y) : x.localeCompare(y);
+        return ascending ? order : -order;
+      });
+      rows.forEach(row => group.appendChild(row));
+    }
+  });
+});
+";
+
+/// Write a single self-contained HTML report: summary statistics, then a table
+/// grouped by category that sorts by clicking a column header and filters by text
+/// and category. Styles and script are inline, so the file makes no requests.
+pub fn write_html(repositories: &[Repository], categories: &HashMap<String, String>, options: &HtmlOptions) -> String {
+    let mut groups: Vec<(String, Vec<&Repository>)> = Vec::new();
+    for repo in repositories {
+        let category = categories
+            .get(&repo.full_name)
+            .map(String::as_str)
+            .unwrap_or(UNCATEGORIZED);
+        match groups.iter_mut().find(|(title, _)| title == category) {
+            Some((_, repos)) => repos.push(repo),
+            None => groups.push((category.to_string(), vec![repo])),
+        }
+    }
+
+    let title = escape_html(&options.title);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<header>\n<h1>{}</h1>\n\
+         <p>{}</p>\n</header>\n",
+        title,
+        HTML_STYLE,
+        title,
+        match &options.scanned_at {
+            Some(scanned_at) => format!("Scanned {}", escape_html(scanned_at)),
+            None => format!("Generated {}", chrono::Utc::now().to_rfc3339()),
+        }
+    );
+
+    html.push_str("<section class=\"stats\">\n");
+    for (label, value) in summary_statistics(repositories, groups.len()) {
+        html.push_str(&format!(
+            "<div class=\"stat\"><strong>{}</strong>{}</div>\n",
+            escape_html(&value),
+            label
+        ));
+    }
+    html.push_str("</section>\n");
+
+    html.push_str(
+        "<div class=\"controls\">\n<input id=\"search\" type=\"search\" placeholder=\"Filter repositories...\">\n\
+         <select id=\"category\">\n<option value=\"\">All categories</option>\n",
+    );
+    for (category, _) in &groups {
+        let category = escape_html(category);
+        html.push_str(&format!("<option value=\"{}\">{}</option>\n", category, category));
+    }
+    html.push_str("</select>\n</div>\n<table>\n<thead>\n<tr>");
+    for (header, numeric) in HTML_COLUMNS {
+        html.push_str(&format!("<th data-numeric=\"{}\">{}</th>", numeric, header));
+    }
+    html.push_str("</tr>\n</thead>\n");
+
+    for (category, repos) in &groups {
+        let category = escape_html(category);
+        html.push_str(&format!(
+            "<tbody data-category=\"{}\">\n<tr class=\"group\"><td colspan=\"{}\">{} ({})</td></tr>\n",
+            category,
+            HTML_COLUMNS.len(),
+            category,
+            repos.len()
+        ));
+        for repo in repos {
+            html.push_str(&html_row(repo));
+        }
+        html.push_str("</tbody>\n");
+    }
+
+    html.push_str(&format!("</table>\n<script>{}</script>\n</body>\n</html>\n", HTML_SCRIPT));
+    html
+}
+
+fn html_row(repo: &Repository) -> String {
+    let description = repo.description.as_deref().unwrap_or_default();
+    let language = repo.language.as_deref().unwrap_or_default();
+    let license = repo.license.as_ref().map(|l| l.name.as_str()).unwrap_or_default();
+    let updated = repo.updated_at.get(..10).unwrap_or(&repo.updated_at);
+    let search = format!("{} {} {}", repo.full_name, description, language).to_lowercase();
+    let text = |value: &str| {
+        let value = escape_html(value);
+        format!("<td data-value=\"{}\">{}</td>", value, value)
+    };
+    let number = |value: u32| format!("<td class=\"number\" data-value=\"{}\">{}</td>", value, value);
+    // Imported files can carry any URL; only web links become clickable, so a
+    // `javascript:` or `data:` URL can't run in the report
+    let name = if repo.html_url.starts_with("https://") || repo.html_url.starts_with("http://") {
+        format!("<a href=\"{}\">{}</a>", escape_html(&repo.html_url), escape_html(&repo.full_name))
+    } else {
+        escape_html(&repo.full_name)
+    };
+
+    format!(
+        "<tr class=\"repo{}\" data-search=\"{}\"><td data-value=\"{}\">{}</td>{}{}{}{}{}{}{}</tr>\n",
+        if repo.archived { " archived" } else { "" },
+        escape_html(&search),
+        escape_html(&repo.full_name.to_lowercase()),
+        name,
+        text(description),
+        number(repo.stargazers_count),
+        number(repo.forks_count),
+        number(repo.open_issues_count),
+        text(language),
+        text(license),
+        text(updated),
+    )
+}
+
+/// Headline figures for the report, as (label, value) pairs
+fn summary_statistics(repositories: &[Repository], category_count: usize) -> Vec<(&'static str, String)> {
+    let total_stars: u64 = repositories.iter().map(|r| r.stargazers_count as u64).sum();
+    let mut stars: Vec<u32> = repositories.iter().map(|r| r.stargazers_count).collect();
+    stars.sort_unstable();
+    let median = stars.get(stars.len() / 2).copied().unwrap_or(0);
+    let archived = repositories.iter().filter(|r| r.archived).count();
+
+    let mut languages: HashMap<&str, usize> = HashMap::new();
+    for language in repositories.iter().filter_map(|r| r.language.as_deref()) {
+        *languages.entry(language).or_insert(0) += 1;
+    }
+    let top_language = languages
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(language, _)| language.to_string())
+        .unwrap_or_else(|| String::from("-"));
+
+    vec![
+        ("Repositories", repositories.len().to_string()),
+        ("Categories", category_count.to_string()),
+        ("Total stars", total_stars.to_string()),
+        ("Median stars", median.to_string()),
+        ("Archived", archived.to_string()),
+        ("Top language", top_language),
+    ]
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// A repository with its list category, as one record of a JSON or NDJSON export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorizedRepository {
+    #[serde(flatten)]
+    pub repository: Repository,
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+fn categorized<'a>(
+    repositories: &'a [Repository],
+    categories: &'a HashMap<String, String>,
+) -> impl Iterator<Item = CategorizedRepository> + 'a {
+    repositories.iter().map(|repo| CategorizedRepository {
+        repository: repo.clone(),
+        category: categories.get(&repo.full_name).cloned(),
+    })
+}
+
+/// Write repositories as a pretty-printed JSON array, each with its `category`
+pub fn write_json(repositories: &[Repository], categories: &HashMap<String, String>) -> Result<String> {
+    let records: Vec<CategorizedRepository> = categorized(repositories, categories).collect();
+    serde_json::to_string_pretty(&records).context("Failed to serialize JSON")
+}
+
+/// Write one compact JSON repository per line, each with its `category`
+pub fn write_ndjson(repositories: &[Repository], categories: &HashMap<String, String>) -> Result<String> {
+    let mut ndjson = String::new();
+    for record in categorized(repositories, categories) {
+        ndjson.push_str(&serde_json::to_string(&record).context("Failed to serialize repository")?);
+        ndjson.push('\n');
+    }
+    Ok(ndjson)
+}
+
+const SQLITE_SCHEMA: &str = "
+    CREATE TABLE licenses (
+        id INTEGER PRIMARY KEY,
+        key TEXT NOT NULL UNIQUE,
+        name TEXT NOT NULL,
+        spdx_id TEXT,
+        url TEXT
+    );
+    CREATE TABLE categories (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE
+    );
+    CREATE TABLE repositories (
+        id INTEGER PRIMARY KEY,
+        forge_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        full_name TEXT NOT NULL,
+        description TEXT,
+        html_url TEXT NOT NULL,
+        homepage TEXT,
+        stargazers_count INTEGER NOT NULL,
+        forks_count INTEGER NOT NULL,
+        open_issues_count INTEGER NOT NULL,
+        language TEXT,
+        license_id INTEGER REFERENCES licenses (id),
+        category_id INTEGER REFERENCES categories (id),
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        pushed_at TEXT,
+        added_to_list_at TEXT,
+        archived INTEGER NOT NULL,
+        disabled INTEGER NOT NULL,
+        fork INTEGER NOT NULL,
+        is_template INTEGER NOT NULL,
+        latest_release TEXT,
+        latest_release_at TEXT,
+        release_count INTEGER,
+        repository TEXT NOT NULL
+    );
+    CREATE TABLE topics (
+        repository_id INTEGER NOT NULL REFERENCES repositories (id),
+        topic TEXT NOT NULL,
+        PRIMARY KEY (repository_id, topic)
+    );
+    CREATE INDEX topics_by_topic ON topics (topic);
+";
+
+/// Write repositories to a new SQLite database at `path`, replacing any file there.
+///
+/// Licenses, categories and topics are normalized into their own tables; each
+/// repository row also keeps the full JSON record in its `repository` column.
+pub fn write_sqlite(repositories: &[Repository], categories: &HashMap<String, String>, path: &Path) -> Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path).context("Failed to replace existing database")?;
+    }
+    let mut conn = Connection::open(path).context("Failed to create database")?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    conn.execute_batch(SQLITE_SCHEMA).context("Failed to create tables")?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut find_license = tx.prepare("SELECT id FROM licenses WHERE key = ?1")?;
+        let mut insert_license = tx.prepare("INSERT INTO licenses (key, name, spdx_id, url) VALUES (?1, ?2, ?3, ?4)")?;
+        let mut find_category = tx.prepare("SELECT id FROM categories WHERE name = ?1")?;
+        let mut insert_category = tx.prepare("INSERT INTO categories (name) VALUES (?1)")?;
+        let mut insert_repository = tx.prepare(
+            "INSERT INTO repositories
+             (forge_id, name, full_name, description, html_url, homepage, stargazers_count, forks_count,
+              open_issues_count, language, license_id, category_id, created_at, updated_at, pushed_at,
+              added_to_list_at, archived, disabled, fork, is_template, latest_release, latest_release_at,
+              release_count, repository)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20,
+                     ?21, ?22, ?23, ?24)",
+        )?;
+        let mut insert_topic = tx.prepare("INSERT OR IGNORE INTO topics (repository_id, topic) VALUES (?1, ?2)")?;
+
+        for repo in repositories {
+            let license_id = match &repo.license {
+                Some(license) => match find_license
+                    .query_row(params![license.key], |row| row.get::<_, i64>(0))
+                    .optional()?
+                {
+                    Some(id) => Some(id),
+                    None => Some(insert_license.insert(params![
+                        license.key,
+                        license.name,
+                        license.spdx_id,
+                        license.url
+                    ])?),
+                },
+                None => None,
+            };
+            let category_id = match categories.get(&repo.full_name) {
+                Some(category) => match find_category
+                    .query_row(params![category], |row| row.get::<_, i64>(0))
+                    .optional()?
+                {
+                    Some(id) => Some(id),
+                    None => Some(insert_category.insert(params![category])?),
+                },
+                None => None,
+            };
+
+            let repository_id = insert_repository.insert(params![
+                repo.id as i64,
+                repo.name,
+                repo.full_name,
+                repo.description,
+                repo.html_url,
+                repo.homepage,
+                repo.stargazers_count,
+                repo.forks_count,
+                repo.open_issues_count,
+                repo.language,
+                license_id,
+                category_id,
+                repo.created_at,
+                repo.updated_at,
+                repo.pushed_at,
+                repo.added_to_list_at,
+                repo.archived,
+                repo.disabled,
+                repo.fork,
+                repo.is_template,
+                repo.latest_release.as_ref().map(|r| &r.tag_name),
+                repo.latest_release.as_ref().and_then(|r| r.published_at.as_ref()),
+                repo.release_count,
+                serde_json::to_string(repo)?,
+            ])?;
+            for topic in &repo.topics {
+                insert_topic.execute(params![repository_id, topic])?;
+            }
+        }
+    }
+    tx.commit().context("Failed to write database")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::github::License;
+    use crate::services::import::read_csv;
+
+    fn repository() -> Repository {
+        Repository {
+            description: Some(String::from("Says \"hi\", then\r\nwaves; twice")),
+            homepage: Some(String::from("https://tool.dev")),
+            stargazers_count: 1200,
+            forks_count: 30,
+            open_issues_count: 4,
+            language: Some(String::from("Rust")),
+            license: Some(License {
+                key: String::from("mit"),
+                name: String::from("MIT License"),
+                spdx_id: Some(String::from("MIT")),
+                url: None,
+            }),
+            topics: vec![String::from("cli"), String::from("terminal")],
+            updated_at: String::from("2024-05-01T00:00:00Z"),
+            ..Repository::base("org/tool")
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let categories: HashMap<String, String> =
+            [(String::from("org/tool"), String::from("Tools, misc"))].into_iter().collect();
+
+        let csv = write_csv(&[repository()], &categories, &CsvOptions::default());
+        let records = read_csv(&csv, ',').unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].fields.len(), ExportColumn::ALL.len());
+        assert_eq!(records[0].fields[0], "Name");
+
+        let cell = |column: ExportColumn| {
+            let i = ExportColumn::ALL.iter().position(|c| *c == column).unwrap();
+            records[1].fields[i].as_str()
+        };
+        assert_eq!(cell(ExportColumn::Description), "Says \"hi\", then\r\nwaves; twice");
+        assert_eq!(cell(ExportColumn::Category), "Tools, misc");
+        assert_eq!(cell(ExportColumn::Topics), "cli;terminal");
+        assert_eq!(cell(ExportColumn::OpenIssues), "4");
+        assert_eq!(cell(ExportColumn::Homepage), "https://tool.dev");
+        assert_eq!(cell(ExportColumn::Releases), "");
+    }
+
+    #[test]
+    fn test_csv_options() {
+        let options = CsvOptions {
+            columns: vec![ExportColumn::FullName, ExportColumn::Topics, ExportColumn::Description],
+            delimiter: ';',
+            topic_separator: String::from(" | "),
+            bom: true,
+        };
+
+        let csv = write_csv(&[repository()], &HashMap::new(), &options);
+        assert!(csv.starts_with("\u{feff}Full Name;Topics;Description\r\n"));
+        // Commas no longer need quoting, semicolons now do
+        assert!(csv.contains("org/tool;cli | terminal;\"Says \"\"hi\"\", then\r\nwaves; twice\"\r\n"));
+        assert_eq!(
+            read_csv(&csv, ';').unwrap()[1].fields,
+            vec!["org/tool", "cli | terminal", "Says \"hi\", then\r\nwaves; twice"]
+        );
+    }
+
+    #[test]
+    fn test_check_delimiter() {
+        assert_eq!(check_delimiter(';').unwrap(), ';');
+        assert_eq!(check_delimiter('\t').unwrap(), '\t');
+        assert!(check_delimiter('"').is_err());
+        assert!(check_delimiter('\r').is_err());
+        assert!(check_delimiter('\n').is_err());
+    }
+
+    #[test]
+    fn test_write_markdown() {
+        let repositories = vec![
+            Repository {
+                name: String::from("small"),
+                full_name: String::from("org/small"),
+                html_url: String::from("https://github.com/org/small"),
+                description: Some(String::from("A small tool")),
+                stargazers_count: 5,
+                license: None,
+                ..repository()
+            },
+            repository(),
+            Repository {
+                name: String::from("old"),
+                full_name: String::from("org/old"),
+                html_url: String::from("https://github.com/org/old"),
+                description: None,
+                archived: true,
+                ..repository()
+            },
+            Repository {
+                name: String::from("loose"),
+                full_name: String::from("org/loose"),
+                html_url: String::from("https://github.com/org/loose"),
+                description: Some(String::from("Not in any category!")),
+                ..repository()
+            },
+        ];
+        let categories: HashMap<String, String> = [
+            (String::from("org/small"), String::from("Tools")),
+            (String::from("org/tool"), String::from("Tools")),
+            (String::from("org/old"), String::from("Tools")),
+        ]
+        .into_iter()
+        .collect();
+        let options = MarkdownOptions {
+            title: String::from("Awesome Tools"),
+            sort: ExportSort::Stars,
+            badges: true,
+        };
+
+        let markdown = write_markdown(&repositories, &categories, &options);
+        assert_eq!(
+            markdown,
+            "# Awesome Tools
+
+## Contents
+
+- [Tools](#tools)
+- [Uncategorized](#uncategorized)
+- [Archived](#archived)
+
+## Tools
+
+- [tool](https://github.com/org/tool) ![Stars](https://img.shields.io/badge/stars-1200-blue) \
+![License](https://img.shields.io/badge/license-MIT-green) - Says \"hi\", then waves; twice.
+- [small](https://github.com/org/small) ![Stars](https://img.shields.io/badge/stars-5-blue) - A small tool.
+
+## Uncategorized
+
+- [loose](https://github.com/org/loose) ![Stars](https://img.shields.io/badge/stars-1200-blue) \
+![License](https://img.shields.io/badge/license-MIT-green) - Not in any category!
+
+## Archived
+
+- [old](https://github.com/org/old) ![Stars](https://img.shields.io/badge/stars-1200-blue) \
+![License](https://img.shields.io/badge/license-MIT-green)
+"
+        );
+    }
+
+    #[test]
+    fn test_write_markdown_edge_cases() {
+        let license = |spdx_id: &str, name: &str| {
+            Some(License {
+                key: String::from("other"),
+                name: name.to_string(),
+                spdx_id: Some(spdx_id.to_string()),
+                url: None,
+            })
+        };
+        let repositories = vec![
+            Repository {
+                license: license("NOASSERTION", "Custom/Commercial #2?"),
+                ..Repository::base("org/custom")
+            },
+            Repository {
+                license: license("BSD-3-Clause", "BSD 3-Clause"),
+                archived: true,
+                ..Repository::base("org/frozen")
+            },
+            Repository::base("org/retired"),
+        ];
+        let categories: HashMap<String, String> = [
+            (String::from("org/custom"), String::from("Tools")),
+            (String::from("org/retired"), String::from("Archived")),
+        ]
+        .into_iter()
+        .collect();
+        let options = MarkdownOptions {
+            sort: ExportSort::Name,
+            badges: true,
+            ..MarkdownOptions::default()
+        };
+
+        let markdown = write_markdown(&repositories, &categories, &options);
+        assert!(markdown.contains("badge/license-Custom%2FCommercial%20%232%3F-green"));
+        assert!(markdown.contains("badge/license-BSD--3--Clause-green"));
+        // The generated section joins the list's own "Archived" category
+        assert_eq!(markdown.matches("## Archived").count(), 1);
+        assert!(!markdown.contains("#archived-1"));
+        assert!(markdown.contains("## Archived\n\n- [frozen]"));
+        assert!(markdown.contains("- [retired](https://github.com/org/retired)"));
+    }
+
+    #[test]
+    fn test_write_html() {
+        let repositories = vec![
+            repository(),
+            Repository {
+                name: String::from("<script>"),
+                full_name: String::from("org/<script>"),
+                description: Some(String::from("Tries </script><b>injection</b>")),
+                html_url: String::from("javascript:alert(1)"),
+                stargazers_count: 10,
+                archived: true,
+                ..repository()
+            },
+        ];
+        let categories: HashMap<String, String> =
+            [(String::from("org/tool"), String::from("Tools & Utilities"))].into_iter().collect();
+        let options = HtmlOptions {
+            title: String::from("Awesome Report"),
+            scanned_at: Some(String::from("2024-06-01T12:00:00+00:00")),
+        };
+
+        let html = write_html(&repositories, &categories, &options);
+        assert!(html.contains("<title>Awesome Report</title>"));
+        assert!(html.contains("<p>Scanned 2024-06-01T12:00:00+00:00</p>"));
+        assert!(html.contains("<strong>1210</strong>Total stars"));
+        assert!(html.contains("<strong>1</strong>Archived"));
+        assert!(html.contains("<tbody data-category=\"Tools &amp; Utilities\">"));
+        assert!(html.contains("Uncategorized (1)"));
+        assert!(html.contains("<tr class=\"repo archived\""));
+        assert!(html.contains("Tries &lt;/script&gt;&lt;b&gt;injection&lt;/b&gt;"));
+        assert!(!html.contains("<b>injection"));
+        assert!(html.contains("<a href=\"https://github.com/org/tool\">org/tool</a>"));
+        assert!(!html.contains("javascript:"));
+        // Self-contained: nothing but the repository links points elsewhere
+        assert!(!html.contains("src=\"http"));
+        assert!(!html.contains("<link"));
+
+        let generated = write_html(
+            &repositories,
+            &categories,
+            &HtmlOptions {
+                scanned_at: None,
+                ..options
+            },
+        );
+        assert!(generated.contains("<p>Generated "));
+        assert!(!generated.contains("Scanned"));
+    }
+
+    #[test]
+    fn test_write_ndjson() {
+        let categories: HashMap<String, String> =
+            [(String::from("org/tool"), String::from("Tools"))].into_iter().collect();
+        let repositories = vec![
+            repository(),
+            Repository {
+                full_name: String::from("org/other"),
+                ..repository()
+            },
+        ];
+
+        let ndjson = write_ndjson(&repositories, &categories).unwrap();
+        let records: Vec<CategorizedRepository> =
+            ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].repository.full_name, "org/tool");
+        assert_eq!(records[0].category.as_deref(), Some("Tools"));
+        assert_eq!(records[1].category, None);
+        assert!(!ndjson.lines().next().unwrap().contains("\n"));
+    }
+
+    #[test]
+    fn test_write_sqlite() {
+        let path = std::env::temp_dir().join(format!("export-test-{}.sqlite3", std::process::id()));
+        let categories: HashMap<String, String> = [
+            (String::from("org/tool"), String::from("Tools")),
+            (String::from("org/other"), String::from("Tools")),
+        ]
+        .into_iter()
+        .collect();
+        let repositories = vec![
+            repository(),
+            Repository {
+                full_name: String::from("org/other"),
+                topics: vec![String::from("cli")],
+                ..repository()
+            },
+        ];
+
+        write_sqlite(&repositories, &categories, &path).unwrap();
+        // Exporting again replaces the database rather than appending to it
+        write_sqlite(&repositories, &categories, &path).unwrap();
+
+        let conn = Connection::open(&path).unwrap();
+        let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM repositories"), 2);
+        assert_eq!(count("SELECT COUNT(*) FROM licenses"), 1);
+        assert_eq!(count("SELECT COUNT(*) FROM categories"), 1);
+        assert_eq!(count("SELECT COUNT(*) FROM topics WHERE topic = 'cli'"), 2);
+        let license: String = conn
+            .query_row(
+                "SELECT l.spdx_id FROM repositories r JOIN licenses l ON l.id = r.license_id WHERE r.full_name = 'org/tool'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(license, "MIT");
+
+        drop(conn);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,377 @@
+use crate::models::github::{Forge, License, Repository};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct GitLabProject {
+    id: u64,
+    name: String,
+    path_with_namespace: String,
+    description: Option<String>,
+    web_url: String,
+    star_count: u32,
+    forks_count: u32,
+    #[serde(default)]
+    open_issues_count: u32,
+    #[serde(default)]
+    topics: Vec<String>,
+    license: Option<GitLabLicense>,
+    #[serde(default)]
+    archived: bool,
+    forked_from_project: Option<serde_json::Value>,
+    last_activity_at: String,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabLicense {
+    key: String,
+    name: String,
+    html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaRepo {
+    id: u64,
+    name: String,
+    full_name: String,
+    description: Option<String>,
+    html_url: String,
+    website: Option<String>,
+    stars_count: u32,
+    forks_count: u32,
+    open_issues_count: u32,
+    language: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
+    licenses: Vec<String>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    template: bool,
+    updated_at: String,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketRepo {
+    name: String,
+    full_name: String,
+    description: Option<String>,
+    website: Option<String>,
+    language: Option<String>,
+    links: BitbucketLinks,
+    updated_on: String,
+    created_on: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketLinks {
+    html: BitbucketLink,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketLink {
+    href: String,
+}
+
+/// Fetches repository metadata from the non-GitHub forges an awesome list can link to,
+/// mapped into the same `Repository` model the GitHub scanner produces
+pub struct ForgeClient {
+    client: reqwest::Client,
+}
+
+impl ForgeClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent("developer-dashboard/0.1.0")
+                .build()
+                .unwrap(),
+        }
+    }
+
+    pub async fn fetch_repository(&self, forge: Forge, host: &str, owner: &str, repo: &str) -> Result<Repository> {
+        match forge {
+            Forge::GitLab => self.fetch_gitlab_repository(host, owner, repo).await,
+            Forge::Gitea => self.fetch_gitea_repository(host, owner, repo).await,
+            Forge::Bitbucket => self.fetch_bitbucket_repository(owner, repo).await,
+            Forge::SourceHut => anyhow::bail!("SourceHut repositories can't be fetched: its API requires an OAuth token"),
+            Forge::GitHub => anyhow::bail!("GitHub repositories are fetched with GitHubClient"),
+        }
+    }
+
+    /// GitLab exposes stars, forks, topics and license, but no primary language or homepage
+    async fn fetch_gitlab_repository(&self, host: &str, owner: &str, repo: &str) -> Result<Repository> {
+        let project_path = format!("{}/{}", owner, repo).replace('/', "%2F");
+        let url = format!("https://{}/api/v4/projects/{}?license=true", host, project_path);
+
+        let response = self.client.get(&url).send().await.context("Failed to fetch GitLab project")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("GitLab API error: {}", response.status());
+        }
+
+        let project: GitLabProject = response.json().await.context("Failed to parse GitLab project")?;
+
+        Ok(project.into())
+    }
+
+    /// Gitea and Forgejo (including Codeberg) mirror most of the GitHub repository fields
+    async fn fetch_gitea_repository(&self, host: &str, owner: &str, repo: &str) -> Result<Repository> {
+        let url = format!("https://{}/api/v1/repos/{}/{}", host, owner, repo);
+
+        let response = self.client.get(&url).send().await.context("Failed to fetch Gitea repository")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Gitea API error: {}", response.status());
+        }
+
+        let gitea_repo: GiteaRepo = response.json().await.context("Failed to parse Gitea repository")?;
+
+        Ok(gitea_repo.into())
+    }
+
+    /// Bitbucket has no stars, issue counts or license in its repository API
+    async fn fetch_bitbucket_repository(&self, workspace: &str, repo: &str) -> Result<Repository> {
+        let url = format!("https://api.bitbucket.org/2.0/repositories/{}/{}", workspace, repo);
+
+        let response = self.client.get(&url).send().await.context("Failed to fetch Bitbucket repository")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Bitbucket API error: {}", response.status());
+        }
+
+        let bitbucket_repo: BitbucketRepo = response.json().await.context("Failed to parse Bitbucket repository")?;
+
+        Ok(bitbucket_repo.into())
+    }
+}
+
+impl Default for ForgeClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<GitLabProject> for Repository {
+    fn from(project: GitLabProject) -> Self {
+        Repository {
+            id: project.id,
+            name: project.name,
+            full_name: project.path_with_namespace,
+            description: project.description.filter(|d| !d.is_empty()),
+            html_url: project.web_url,
+            homepage: None,
+            stargazers_count: project.star_count,
+            forks_count: project.forks_count,
+            open_issues_count: project.open_issues_count,
+            language: None,
+            license: project.license.map(|l| License {
+                key: l.key,
+                name: l.name,
+                // GitLab only has its own license keys and display nicknames such as "GNU GPLv3"
+                spdx_id: None,
+                url: l.html_url,
+            }),
+            topics: project.topics,
+            updated_at: project.last_activity_at,
+            created_at: project.created_at,
+            pushed_at: None,
+            added_to_list_at: None,
+            archived: project.archived,
+            disabled: false,
+            fork: project.forked_from_project.is_some(),
+            is_template: false,
+            linked_full_name: None,
+            latest_release: None,
+            release_count: None,
+            activity: None,
+        }
+    }
+}
+
+impl From<GiteaRepo> for Repository {
+    fn from(gitea_repo: GiteaRepo) -> Self {
+        Repository {
+            id: gitea_repo.id,
+            name: gitea_repo.name,
+            full_name: gitea_repo.full_name,
+            description: gitea_repo.description.filter(|d| !d.is_empty()),
+            html_url: gitea_repo.html_url,
+            homepage: gitea_repo.website.filter(|w| !w.is_empty()),
+            stargazers_count: gitea_repo.stars_count,
+            forks_count: gitea_repo.forks_count,
+            open_issues_count: gitea_repo.open_issues_count,
+            language: gitea_repo.language.filter(|l| !l.is_empty()),
+            license: gitea_repo.licenses.into_iter().next().map(|spdx| License {
+                key: spdx.to_lowercase(),
+                name: spdx.clone(),
+                spdx_id: Some(spdx),
+                url: None,
+            }),
+            topics: gitea_repo.topics,
+            updated_at: gitea_repo.updated_at,
+            created_at: gitea_repo.created_at,
+            pushed_at: None,
+            added_to_list_at: None,
+            archived: gitea_repo.archived,
+            disabled: false,
+            fork: gitea_repo.fork,
+            is_template: gitea_repo.template,
+            linked_full_name: None,
+            latest_release: None,
+            release_count: None,
+            activity: None,
+        }
+    }
+}
+
+impl From<BitbucketRepo> for Repository {
+    fn from(bitbucket_repo: BitbucketRepo) -> Self {
+        Repository {
+            id: 0,
+            name: bitbucket_repo.name,
+            full_name: bitbucket_repo.full_name,
+            description: bitbucket_repo.description.filter(|d| !d.is_empty()),
+            html_url: bitbucket_repo.links.html.href,
+            homepage: bitbucket_repo.website.filter(|w| !w.is_empty()),
+            stargazers_count: 0,
+            forks_count: 0,
+            open_issues_count: 0,
+            language: bitbucket_repo.language.filter(|l| !l.is_empty()),
+            license: None,
+            topics: Vec::new(),
+            updated_at: bitbucket_repo.updated_on,
+            created_at: bitbucket_repo.created_on,
+            pushed_at: None,
+            added_to_list_at: None,
+            archived: false,
+            disabled: false,
+            fork: false,
+            is_template: false,
+            linked_full_name: None,
+            latest_release: None,
+            release_count: None,
+            activity: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitlab_project() {
+        let project: GitLabProject = serde_json::from_str(
+            r#"{
+                "id": 278964,
+                "name": "GitLab",
+                "path_with_namespace": "gitlab-org/gitlab",
+                "description": "",
+                "web_url": "https://gitlab.com/gitlab-org/gitlab",
+                "star_count": 5000,
+                "forks_count": 10000,
+                "topics": ["devops"],
+                "license": {
+                    "key": "gpl-3.0",
+                    "name": "GNU General Public License v3.0",
+                    "nickname": "GNU GPLv3",
+                    "html_url": "https://choosealicense.com/licenses/gpl-3.0/"
+                },
+                "forked_from_project": {"id": 1},
+                "last_activity_at": "2024-05-01T00:00:00.000Z",
+                "created_at": "2016-01-01T00:00:00.000Z"
+            }"#,
+        )
+        .unwrap();
+
+        let repository = Repository::from(project);
+        assert_eq!(repository.full_name, "gitlab-org/gitlab");
+        assert_eq!(repository.description, None);
+        assert_eq!(repository.stargazers_count, 5000);
+        assert_eq!(repository.open_issues_count, 0);
+        assert_eq!(repository.topics, vec!["devops"]);
+        assert!(repository.fork);
+        assert!(!repository.archived);
+        let license = repository.license.unwrap();
+        assert_eq!(license.key, "gpl-3.0");
+        assert_eq!(license.name, "GNU General Public License v3.0");
+        assert_eq!(license.spdx_id, None);
+        assert_eq!(repository.updated_at, "2024-05-01T00:00:00.000Z");
+    }
+
+    #[test]
+    fn test_gitea_repo() {
+        let gitea_repo: GiteaRepo = serde_json::from_str(
+            r#"{
+                "id": 1,
+                "name": "forgejo",
+                "full_name": "forgejo/forgejo",
+                "description": "Beyond coding. We forge.",
+                "html_url": "https://codeberg.org/forgejo/forgejo",
+                "website": "",
+                "stars_count": 2000,
+                "forks_count": 400,
+                "open_issues_count": 900,
+                "language": "Go",
+                "licenses": ["GPL-3.0-or-later"],
+                "template": true,
+                "updated_at": "2024-05-01T00:00:00Z",
+                "created_at": "2022-01-01T00:00:00Z"
+            }"#,
+        )
+        .unwrap();
+
+        let repository = Repository::from(gitea_repo);
+        assert_eq!(repository.full_name, "forgejo/forgejo");
+        assert_eq!(repository.homepage, None);
+        assert_eq!(repository.language.as_deref(), Some("Go"));
+        assert_eq!(repository.open_issues_count, 900);
+        assert!(repository.is_template);
+        assert!(repository.topics.is_empty());
+        let license = repository.license.unwrap();
+        assert_eq!(license.key, "gpl-3.0-or-later");
+        assert_eq!(license.spdx_id.as_deref(), Some("GPL-3.0-or-later"));
+    }
+
+    #[test]
+    fn test_bitbucket_repo() {
+        let bitbucket_repo: BitbucketRepo = serde_json::from_str(
+            r#"{
+                "name": "python-bitbucket",
+                "full_name": "atlassian/python-bitbucket",
+                "description": "Python client",
+                "website": "https://example.com",
+                "language": "python",
+                "links": {"html": {"href": "https://bitbucket.org/atlassian/python-bitbucket"}},
+                "updated_on": "2024-05-01T00:00:00+00:00",
+                "created_on": "2015-01-01T00:00:00+00:00"
+            }"#,
+        )
+        .unwrap();
+
+        let repository = Repository::from(bitbucket_repo);
+        assert_eq!(repository.id, 0);
+        assert_eq!(repository.full_name, "atlassian/python-bitbucket");
+        assert_eq!(repository.html_url, "https://bitbucket.org/atlassian/python-bitbucket");
+        assert_eq!(repository.homepage.as_deref(), Some("https://example.com"));
+        assert_eq!(repository.language.as_deref(), Some("python"));
+        assert_eq!(repository.stargazers_count, 0);
+        assert!(repository.license.is_none());
+        assert_eq!(repository.created_at, "2015-01-01T00:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn test_sourcehut_unsupported() {
+        let error = ForgeClient::new()
+            .fetch_repository(Forge::SourceHut, "git.sr.ht", "~sircmpwn", "aerc")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("SourceHut"));
+    }
+}
@@ -1,8 +1,19 @@
-use crate::models::github::{Repository, License};
+use crate::models::github::{
+    Forge, HealthSignals, LanguageBytes, License, RateLimitInfo, Release, Repository, RepositoryActivity,
+    RepositoryFetchResult,
+};
+use crate::services::added_dates::{added_repo_links, AdditionsCache, CommitAdditions};
+use crate::services::http_cache::{fingerprint, HttpCache};
+use crate::services::markdown::{entry_category, parse_awesome_list};
+use crate::services::rate_limit::{retry_delay, RateLimitTracker, MAX_RATE_LIMIT_RETRIES};
 use anyhow::{Context, Result};
-use regex::Regex;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Deserialize)]
 struct GitHubRepo {
@@ -18,8 +29,17 @@ struct GitHubRepo {
     language: Option<String>,
     license: Option<GitHubLicense>,
     topics: Vec<String>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    is_template: bool,
     updated_at: String,
     created_at: String,
+    pushed_at: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +50,19 @@ struct GitHubLicense {
     url: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    resources: HashMap<String, RateLimitResource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResource {
+    limit: u32,
+    remaining: u32,
+    used: u32,
+    reset: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct GitHubCommit {
     sha: String,
@@ -39,7 +72,19 @@ struct GitHubCommit {
 #[derive(Debug, Deserialize)]
 struct CommitData {
     author: CommitAuthor,
-    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCommitDetail {
+    #[serde(default)]
+    files: Vec<GitHubCommitFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCommitFile {
+    filename: String,
+    /// Omitted by GitHub for binary files and very large diffs
+    patch: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,56 +92,402 @@ struct CommitAuthor {
     date: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct GitHubContributorStats {
+    total: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubWeeklyCommits {
+    total: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubWorkflows {
+    total_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    name: Option<String>,
+    published_at: Option<String>,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+/// How often a long wait checks whether its scan was cancelled
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Window the recent commit count of a health score covers
+const RECENT_COMMITS_DAYS: i64 = 90;
+
+/// Statistics endpoints answer 202 while GitHub computes them in the background;
+/// retry with doubling delays (2 + 4 + 8 + 16 seconds) before giving up
+const STATS_RETRIES: u32 = 4;
+const STATS_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Largest page size the commits API allows
+const COMMITS_PER_PAGE: usize = 100;
+
+/// Repositories requested per GraphQL query; GitHub caps node lookups at 100
+pub const GRAPHQL_BATCH_SIZE: usize = 100;
+
+/// How long cached responses are served without revalidating; revalidation is a
+/// conditional request, and a 304 reply doesn't count against the quota
+const README_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+const REPOSITORY_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+const COMMITS_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+const GRAPHQL_REPOSITORY_FIELDS: &str = "
+fragment RepositoryFields on Repository {
+  databaseId
+  name
+  nameWithOwner
+  description
+  url
+  homepageUrl
+  stargazerCount
+  forkCount
+  issues(states: OPEN) { totalCount }
+  pullRequests(states: OPEN) { totalCount }
+  primaryLanguage { name }
+  licenseInfo { key name spdxId url }
+  repositoryTopics(first: 20) { nodes { topic { name } } }
+  isArchived
+  isDisabled
+  isFork
+  isTemplate
+  updatedAt
+  createdAt
+  pushedAt
+}";
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse {
+    data: Option<HashMap<String, Option<GraphQlRepo>>>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    path: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlRepo {
+    database_id: Option<u64>,
+    name: String,
+    name_with_owner: String,
+    description: Option<String>,
+    url: String,
+    homepage_url: Option<String>,
+    stargazer_count: u32,
+    fork_count: u32,
+    issues: GraphQlCount,
+    pull_requests: GraphQlCount,
+    primary_language: Option<GraphQlName>,
+    license_info: Option<GraphQlLicense>,
+    repository_topics: GraphQlTopics,
+    #[serde(default)]
+    is_archived: bool,
+    #[serde(default)]
+    is_disabled: bool,
+    #[serde(default)]
+    is_fork: bool,
+    #[serde(default)]
+    is_template: bool,
+    updated_at: String,
+    created_at: String,
+    pushed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlCount {
+    total_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlName {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlLicense {
+    key: String,
+    name: String,
+    spdx_id: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlTopics {
+    nodes: Vec<GraphQlTopicNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlTopicNode {
+    topic: GraphQlName,
+}
+
+/// A GitHub URL pointing at an awesome list, optionally at a specific branch, tag or file
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubListUrl {
+    pub owner: String,
+    pub repo: String,
+    pub git_ref: Option<String>,
+    /// File or directory within the repository; `None` means the root README
+    pub path: Option<String>,
+    /// Whether `path` names a file (`/blob/` and raw URLs) rather than a directory (`/tree/`)
+    pub path_is_file: bool,
+}
+
+#[derive(Clone)]
 pub struct GitHubClient {
     client: reqwest::Client,
     token: Option<String>,
+    rate_limit: Arc<RateLimitTracker>,
+    cache: Option<Arc<HttpCache>>,
+    force_refresh: bool,
+    cancelled: Option<Arc<AtomicBool>>,
 }
 
 impl GitHubClient {
-    pub fn new(token: Option<String>) -> Self {
+    pub fn new(token: Option<String>, rate_limit: Arc<RateLimitTracker>) -> Self {
         Self {
             client: reqwest::Client::builder()
                 .user_agent("developer-dashboard/0.1.0")
                 .build()
                 .unwrap(),
             token,
+            rate_limit,
+            cache: None,
+            force_refresh: false,
+            cancelled: None,
         }
     }
 
-    pub async fn fetch_readme(&self, owner: &str, repo: &str) -> Result<String> {
-        let url = format!("https://api.github.com/repos/{}/{}/readme", owner, repo);
+    /// Serve GET requests from `cache`; with `force_refresh` every cached response is revalidated
+    pub fn with_cache(mut self, cache: Arc<HttpCache>, force_refresh: bool) -> Self {
+        self.cache = Some(cache);
+        self.force_refresh = force_refresh;
+        self
+    }
+
+    /// Abandon rate limit waits as soon as `cancelled` is set, failing the request
+    pub fn with_cancel(mut self, cancelled: Arc<AtomicBool>) -> Self {
+        self.cancelled = Some(cancelled);
+        self
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.token.is_some()
+    }
 
-        let mut request = self.client.get(&url);
+    /// Send a request with authentication, recording the quota GitHub reports and
+    /// waiting out primary, secondary (abuse) and 429 rate limits before retrying
+    async fn send(&self, mut request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
         if let Some(token) = &self.token {
             request = request.header("Authorization", format!("Bearer {}", token));
         }
-        request = request.header("Accept", "application/vnd.github.v3.raw");
 
-        let response = request.send().await.context("Failed to fetch README")?;
+        let mut attempt = 0;
+        loop {
+            let retry = request.try_clone();
+            let response = request.send().await?;
+            self.rate_limit.update_from_headers(response.headers());
 
-        if !response.status().is_success() {
-            anyhow::bail!("GitHub API error: {}", response.status());
+            let status = response.status();
+            if status != reqwest::StatusCode::FORBIDDEN && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            let headers = response.headers().clone();
+            let body = response.text().await.unwrap_or_default();
+            let delay = retry_delay(status, &headers, attempt, body.to_lowercase().contains("rate limit"));
+
+            match (delay, retry) {
+                (Some(delay), Some(next)) if attempt < MAX_RATE_LIMIT_RETRIES => {
+                    self.wait(delay).await?;
+                    request = next;
+                    attempt += 1;
+                }
+                (Some(_), _) => {
+                    anyhow::bail!("Rate limit exceeded. Please add a GitHub token or wait before retrying.")
+                }
+                (None, _) => return Err(GitHubApiError { status }.into()),
+            }
         }
+    }
 
-        let content = response.text().await.context("Failed to read README content")?;
-        Ok(content)
+    /// Sleep for `delay`, or fail early once the client's cancel flag is set
+    async fn wait(&self, delay: Duration) -> Result<()> {
+        let Some(cancelled) = &self.cancelled else {
+            tokio::time::sleep(delay).await;
+            return Ok(());
+        };
+
+        let cancellation = async {
+            while !cancelled.load(Ordering::SeqCst) {
+                tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+            }
+        };
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => Ok(()),
+            _ = cancellation => anyhow::bail!("Scan cancelled"),
+        }
     }
 
-    pub async fn fetch_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
-        let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+    /// Cache key of a GET request. Responses depend on what the token may see, so
+    /// anonymous and per-token responses are cached separately.
+    fn cache_key(&self, url: &str, accept: Option<&str>) -> String {
+        let auth = match &self.token {
+            Some(token) => format!("token:{}", fingerprint(token)),
+            None => String::from("anonymous"),
+        };
+        format!("{} {} {}", auth, accept.unwrap_or("*/*"), url)
+    }
 
-        let mut request = self.client.get(&url);
-        if let Some(token) = &self.token {
-            request = request.header("Authorization", format!("Bearer {}", token));
+    /// GET a resource through the cache: fresh entries are returned without a request,
+    /// stale ones are revalidated with their ETag / Last-Modified validators
+    async fn get_cached(&self, url: &str, accept: Option<&str>, ttl: Duration) -> Result<String> {
+        let key = self.cache_key(url, accept);
+        let cached = self.cache.as_ref().and_then(|cache| cache.get(&key));
+
+        if let Some(entry) = &cached {
+            if !self.force_refresh && entry.is_fresh(ttl) {
+                return Ok(entry.body.clone());
+            }
         }
 
-        let response = request.send().await.context("Failed to fetch repository")?;
+        let mut request = self.client.get(url);
+        if let Some(accept) = accept {
+            request = request.header("Accept", accept);
+        }
+        if let Some(entry) = &cached {
+            request = entry.apply_validators(request);
+        }
+
+        let response = self.send(request).await?;
+
+        if let (Some(cache), Some(entry)) = (&self.cache, cached) {
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                return Ok(cache.touch(entry).body);
+            }
+        }
+
+        if !response.status().is_success() {
+            return Err(GitHubApiError { status: response.status() }.into());
+        }
+
+        let headers = response.headers().clone();
+        let body = response.text().await.context("Failed to read content")?;
+        if let Some(cache) = &self.cache {
+            cache.put(&key, &headers, &body);
+        }
+
+        Ok(body)
+    }
+
+    /// Refresh the tracked quota from `/rate_limit`, which doesn't count against it
+    pub async fn fetch_rate_limit(&self) -> Result<Vec<RateLimitInfo>> {
+        let response = self
+            .send(self.client.get("https://api.github.com/rate_limit"))
+            .await
+            .context("Failed to fetch rate limit")?;
 
         if !response.status().is_success() {
             anyhow::bail!("GitHub API error: {}", response.status());
         }
 
-        let github_repo: GitHubRepo = response.json().await.context("Failed to parse repository data")?;
+        let body: RateLimitResponse = response.json().await.context("Failed to parse rate limit")?;
+        for (resource, quota) in body.resources {
+            self.rate_limit.update(RateLimitInfo {
+                resource,
+                limit: quota.limit,
+                remaining: quota.remaining,
+                used: quota.used,
+                reset: quota.reset,
+            });
+        }
+
+        Ok(self.rate_limit.snapshot())
+    }
+
+    pub async fn fetch_readme(&self, owner: &str, repo: &str) -> Result<String> {
+        self.fetch_readme_at(owner, repo, None, None).await
+    }
+
+    /// Fetch the README of a directory (the repository root when `dir` is `None`) at a ref
+    pub async fn fetch_readme_at(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<String> {
+        let url = api_url(
+            &["repos", owner, repo, "readme", dir.unwrap_or_default()],
+            &git_ref.map(|git_ref| ("ref", git_ref)).into_iter().collect::<Vec<_>>(),
+        );
+
+        self.fetch_raw(&url).await.context("Failed to fetch README")
+    }
+
+    /// Fetch the raw content of a file at a ref
+    pub async fn fetch_file(&self, owner: &str, repo: &str, git_ref: Option<&str>, path: &str) -> Result<String> {
+        let url = api_url(
+            &["repos", owner, repo, "contents", path],
+            &git_ref.map(|git_ref| ("ref", git_ref)).into_iter().collect::<Vec<_>>(),
+        );
+
+        self.fetch_raw(&url).await.with_context(|| format!("Failed to fetch {}", path))
+    }
+
+    /// Fetch the Markdown a list URL points at: a specific file, a directory README or the root README
+    pub async fn fetch_list_markdown(&self, list: &GitHubListUrl) -> Result<String> {
+        match (&list.path, list.path_is_file) {
+            (Some(path), true) => self.fetch_file(&list.owner, &list.repo, list.git_ref.as_deref(), path).await,
+            (path, _) => {
+                self.fetch_readme_at(&list.owner, &list.repo, list.git_ref.as_deref(), path.as_deref())
+                    .await
+            }
+        }
+    }
+
+    async fn fetch_raw(&self, url: &str) -> Result<String> {
+        self.get_cached(url, Some("application/vnd.github.v3.raw"), README_CACHE_TTL)
+            .await
+    }
+
+    pub async fn fetch_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
+        let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+
+        // A renamed or transferred repository answers with a 301 to its new location,
+        // which reqwest follows; the body then carries the canonical `full_name`
+        let body = match self.get_cached(&url, None, REPOSITORY_CACHE_TTL).await {
+            Ok(body) => body,
+            Err(e) => match e.downcast_ref::<GitHubApiError>() {
+                Some(api_error) if api_error.is_gone() => {
+                    return Err(RepositoryUnavailable {
+                        full_name: format!("{}/{}", owner, repo),
+                        reason: api_error.status.to_string(),
+                    }
+                    .into())
+                }
+                _ => return Err(e.context("Failed to fetch repository")),
+            },
+        };
+
+        let github_repo: GitHubRepo = serde_json::from_str(&body).context("Failed to parse repository data")?;
 
         Ok(Repository {
             id: github_repo.id,
@@ -118,107 +509,604 @@ impl GitHubClient {
             topics: github_repo.topics,
             updated_at: github_repo.updated_at,
             created_at: github_repo.created_at,
+            pushed_at: github_repo.pushed_at,
             added_to_list_at: None,
+            archived: github_repo.archived,
+            disabled: github_repo.disabled,
+            fork: github_repo.fork,
+            is_template: github_repo.is_template,
+            linked_full_name: None,
+            latest_release: None,
+            release_count: None,
+            activity: None,
         })
     }
 
+    /// Fetch the latest release of a repository and how many releases it has published
+    pub async fn fetch_releases(&self, owner: &str, repo: &str) -> Result<(Option<Release>, u32)> {
+        let url = format!("https://api.github.com/repos/{}/{}/releases", owner, repo);
+        let (releases, count) = self
+            .fetch_first_with_count::<GitHubRelease>(&url)
+            .await
+            .context("Failed to fetch releases")?;
+
+        let latest = releases.into_iter().next().map(|release| Release {
+            tag_name: release.tag_name,
+            name: release.name.filter(|name| !name.is_empty()),
+            published_at: release.published_at,
+            prerelease: release.prerelease,
+        });
+
+        Ok((latest, count))
+    }
+
+    /// Gather the activity signals a health score is computed from: the repository
+    /// itself, commits over the last 90 days, closed issues, releases, contributors
+    /// and GitHub Actions workflows, at six requests per repository.
+    pub async fn fetch_health_signals(&self, owner: &str, repo: &str) -> Result<(Repository, HealthSignals)> {
+        let repository = self.fetch_repository(owner, repo).await?;
+        let (owner, repo) = repository.full_name.split_once('/').unwrap_or((owner, repo));
+        let base = format!("https://api.github.com/repos/{}/{}", owner, repo);
+
+        let since = (chrono::Utc::now() - chrono::Duration::days(RECENT_COMMITS_DAYS))
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let commits_url = format!("{}/commits?since={}", base, since);
+        let (_, recent_commits) = self
+            .fetch_first_with_count::<serde_json::Value>(&commits_url)
+            .await
+            .context("Failed to fetch recent commits")?;
+        let (_, closed_issues) = self
+            .fetch_first_with_count::<serde_json::Value>(&format!("{}/issues?state=closed", base))
+            .await
+            .context("Failed to fetch closed issues")?;
+        let contributors = match self
+            .fetch_first_with_count::<serde_json::Value>(&format!("{}/contributors?anon=true", base))
+            .await
+        {
+            Ok((_, contributors)) => Some(contributors),
+            // "The history or contributor list is too large to list contributors for this repository"
+            Err(e) if e.downcast_ref::<GitHubApiError>().is_some_and(|e| e.status == reqwest::StatusCode::FORBIDDEN) => {
+                None
+            }
+            Err(e) => return Err(e.context("Failed to fetch contributors")),
+        };
+        let (latest_release, release_count) = self.fetch_releases(owner, repo).await?;
+
+        let response = self
+            .send(self.client.get(format!("{}/actions/workflows?per_page=1", base)))
+            .await
+            .context("Failed to fetch workflows")?;
+        let has_ci = match response.status() {
+            status if status.is_success() => {
+                let workflows: GitHubWorkflows = response.json().await.context("Failed to parse workflows")?;
+                workflows.total_count > 0
+            }
+            // Repositories with Actions disabled answer 404
+            reqwest::StatusCode::NOT_FOUND => false,
+            status => return Err(anyhow::Error::new(GitHubApiError { status }).context("Failed to fetch workflows")),
+        };
+
+        let signals = HealthSignals {
+            pushed_at: repository.pushed_at.clone(),
+            recent_commits,
+            open_issues: repository.open_issues_count,
+            closed_issues,
+            release_count,
+            latest_release_at: latest_release.and_then(|r| r.published_at),
+            contributors,
+            archived: repository.archived,
+            has_ci,
+        };
+        Ok((repository, signals))
+    }
+
+    /// Fetch contributor, weekly commit and language statistics. Statistics GitHub is
+    /// still computing after every retry are left as `None` rather than failing.
+    pub async fn fetch_activity(&self, owner: &str, repo: &str) -> Result<RepositoryActivity> {
+        let base = format!("https://api.github.com/repos/{}/{}", owner, repo);
+
+        let contributors: Option<Vec<GitHubContributorStats>> = self
+            .fetch_stats(&format!("{}/stats/contributors", base))
+            .await
+            .context("Failed to fetch contributor statistics")?;
+        let weeks: Option<Vec<GitHubWeeklyCommits>> = self
+            .fetch_stats(&format!("{}/stats/commit_activity", base))
+            .await
+            .context("Failed to fetch commit activity")?;
+
+        let body = self
+            .get_cached(&format!("{}/languages", base), None, REPOSITORY_CACHE_TTL)
+            .await
+            .context("Failed to fetch languages")?;
+        let languages: HashMap<String, u64> = serde_json::from_str(&body).context("Failed to parse languages")?;
+        let mut languages: Vec<LanguageBytes> = languages
+            .into_iter()
+            .map(|(name, bytes)| LanguageBytes { name, bytes })
+            .collect();
+        languages.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+
+        let commits: Option<Vec<u32>> = contributors.map(|contributors| contributors.iter().map(|c| c.total).collect());
+
+        Ok(RepositoryActivity {
+            contributor_count: commits.as_ref().map(|c| c.len() as u32),
+            top_contributor_share: commits.as_deref().and_then(top_contributor_share),
+            weekly_commits: weeks.map(|weeks| weeks.iter().map(|w| w.total).collect()),
+            languages,
+        })
+    }
+
+    /// GET a statistics endpoint, retrying while GitHub answers 202 Accepted. Returns
+    /// `None` when the statistics are still being computed or the repository is empty.
+    async fn fetch_stats<T: DeserializeOwned>(&self, url: &str) -> Result<Option<T>> {
+        let mut delay = STATS_RETRY_DELAY;
+        for attempt in 0..=STATS_RETRIES {
+            let response = self.send(self.client.get(url)).await?;
+            match response.status() {
+                reqwest::StatusCode::ACCEPTED if attempt < STATS_RETRIES => {
+                    self.wait(delay).await?;
+                    delay *= 2;
+                }
+                reqwest::StatusCode::ACCEPTED | reqwest::StatusCode::NO_CONTENT => return Ok(None),
+                status if status.is_success() => {
+                    return Ok(Some(response.json().await.context("Failed to parse statistics")?))
+                }
+                status => return Err(GitHubApiError { status }.into()),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Request a single item of a paginated list and read the total item count from
+    /// the page number of the `rel="last"` link, which GitHub omits when everything
+    /// fits on the first page. Empty repositories answer 204 or 409 and count as empty.
+    async fn fetch_first_with_count<T: DeserializeOwned>(&self, url: &str) -> Result<(Vec<T>, u32)> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let response = self
+            .send(self.client.get(format!("{}{}per_page=1", url, separator)))
+            .await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NO_CONTENT || status == reqwest::StatusCode::CONFLICT {
+            return Ok((Vec::new(), 0));
+        }
+        if !status.is_success() {
+            return Err(GitHubApiError { status }.into());
+        }
+
+        let last_page = response
+            .headers()
+            .get(reqwest::header::LINK)
+            .and_then(|link| link.to_str().ok())
+            .and_then(last_page_number);
+        let items: Vec<T> = response.json().await.context("Failed to parse response")?;
+
+        let count = last_page.unwrap_or(items.len() as u32);
+        Ok((items, count))
+    }
+
+    /// Fetch many repositories, batching them into GraphQL queries of up to 100 aliased
+    /// lookups when a token is configured, and falling back to one REST call per
+    /// repository otherwise. Results are returned in request order, and a repository
+    /// that is deleted, private or otherwise unavailable only fails its own result.
+    pub async fn fetch_repositories_bulk(&self, repos: &[(String, String)]) -> Vec<RepositoryFetchResult> {
+        let mut results = Vec::with_capacity(repos.len());
+
+        if self.token.is_none() {
+            for (owner, repo) in repos {
+                let result = self.fetch_repository(owner, repo).await;
+                results.push(RepositoryFetchResult::from_result("github.com", owner, repo, result));
+            }
+            return results;
+        }
+
+        for chunk in repos.chunks(GRAPHQL_BATCH_SIZE) {
+            match self.fetch_repository_batch(chunk).await {
+                Ok(batch) => results.extend(batch),
+                Err(e) => results.extend(chunk.iter().map(|(owner, repo)| RepositoryFetchResult {
+                    host: String::from("github.com"),
+                    owner: owner.clone(),
+                    repo: repo.clone(),
+                    repository: None,
+                    error: Some(e.to_string()),
+                    unavailable: false,
+                })),
+            }
+        }
+
+        results
+    }
+
+    async fn fetch_repository_batch(&self, repos: &[(String, String)]) -> Result<Vec<RepositoryFetchResult>> {
+        let mut variables = serde_json::Map::new();
+        let mut declarations = Vec::new();
+        let mut selections = Vec::new();
+        for (i, (owner, repo)) in repos.iter().enumerate() {
+            variables.insert(format!("o{}", i), serde_json::Value::String(owner.clone()));
+            variables.insert(format!("n{}", i), serde_json::Value::String(repo.clone()));
+            declarations.push(format!("$o{i}: String!, $n{i}: String!", i = i));
+            selections.push(format!(
+                "r{i}: repository(owner: $o{i}, name: $n{i}) {{ ...RepositoryFields }}",
+                i = i
+            ));
+        }
+        let query = format!(
+            "query({}) {{\n{}\n}}\n{}",
+            declarations.join(", "),
+            selections.join("\n"),
+            GRAPHQL_REPOSITORY_FIELDS
+        );
+
+        let request = self
+            .client
+            .post("https://api.github.com/graphql")
+            .json(&serde_json::json!({ "query": query, "variables": variables }));
+
+        let response = self.send(request).await.context("Failed to query GitHub GraphQL API")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("GitHub GraphQL API error: {}", response.status());
+        }
+
+        let body: GraphQlResponse = response.json().await.context("Failed to parse GraphQL response")?;
+
+        if body.data.is_none() {
+            let message = body.errors.first().map(|e| e.message.as_str()).unwrap_or("no data returned");
+            anyhow::bail!("GitHub GraphQL API error: {}", message);
+        }
+        let mut data = body.data.unwrap_or_default();
+
+        let mut errors: HashMap<String, GraphQlError> = HashMap::new();
+        for error in body.errors {
+            if let Some(alias) = error.path.first().and_then(|p| p.as_str()) {
+                errors.insert(alias.to_string(), error);
+            }
+        }
+
+        Ok(repos
+            .iter()
+            .enumerate()
+            .map(|(i, (owner, repo))| {
+                let alias = format!("r{}", i);
+                // GraphQL resolves renamed and transferred repositories to their new name
+                let repository = data
+                    .remove(&alias)
+                    .flatten()
+                    .map(|r| with_linked_name(Repository::from(r), owner, repo));
+                let (error, unavailable) = match (&repository, errors.remove(&alias)) {
+                    (Some(_), _) => (None, false),
+                    (None, Some(error)) => (Some(error.message), error.kind.as_deref() == Some("NOT_FOUND")),
+                    (None, None) => (Some(String::from("Repository not found")), true),
+                };
+                RepositoryFetchResult {
+                    host: String::from("github.com"),
+                    owner: owner.clone(),
+                    repo: repo.clone(),
+                    repository,
+                    error,
+                    unavailable,
+                }
+            })
+            .collect())
+    }
+
+    /// Find when each repository was added to a list by walking the full history of the
+    /// list file, oldest commit first, and inspecting each commit's patch for the first
+    /// `+` line linking the repository. Commits already in `additions` aren't fetched
+    /// again, and newly inspected ones are recorded there even if a later request fails.
     pub async fn fetch_commit_history_for_repos(
         &self,
-        owner: &str,
-        repo: &str,
+        list: &GitHubListUrl,
         repo_urls: &[(String, String)],
+        additions: &mut AdditionsCache,
     ) -> Result<HashMap<String, String>> {
         let mut dates = HashMap::new();
+        let path = list.markdown_path();
 
-        // Fetch commits for README.md ONCE and reuse for all repositories
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/commits?path=README.md&per_page=100",
-            owner, repo
-        );
+        // List every commit that touched the list file; GitHub returns them newest first
+        let mut commits: Vec<GitHubCommit> = Vec::new();
+        for page in 1.. {
+            let per_page = COMMITS_PER_PAGE.to_string();
+            let page = page.to_string();
+            let mut query = vec![("path", path.as_str()), ("per_page", &per_page), ("page", &page)];
+            if let Some(git_ref) = &list.git_ref {
+                query.push(("sha", git_ref));
+            }
+            let url = api_url(&["repos", &list.owner, &list.repo, "commits"], &query);
 
-        let mut request = self.client.get(&url);
-        if let Some(token) = &self.token {
-            request = request.header("Authorization", format!("Bearer {}", token));
+            let body = self
+                .get_cached(&url, None, COMMITS_CACHE_TTL)
+                .await
+                .context("Failed to fetch commits")?;
+            let page_commits: Vec<GitHubCommit> = serde_json::from_str(&body).context("Failed to parse commits")?;
+            let last_page = page_commits.len() < COMMITS_PER_PAGE;
+            commits.extend(page_commits);
+            if last_page {
+                break;
+            }
         }
 
-        let response = request.send().await?;
+        let mut pending: HashMap<String, String> = repo_urls
+            .iter()
+            .map(|(owner, repo)| (format!("{}/{}", owner, repo).to_lowercase(), format!("{}/{}", owner, repo)))
+            .collect();
 
-        if response.status().as_u16() == 403 {
-            anyhow::bail!("Rate limit exceeded. Please add a GitHub token or wait before retrying.");
+        for commit in commits.iter().rev() {
+            if pending.is_empty() {
+                break;
+            }
+
+            if !additions.commits.contains_key(&commit.sha) {
+                let repos = self.fetch_commit_additions(list, &commit.sha, &path).await?;
+                additions.commits.insert(
+                    commit.sha.clone(),
+                    CommitAdditions {
+                        date: commit.commit.author.date.clone(),
+                        repos,
+                    },
+                );
+            }
+
+            let added = &additions.commits[&commit.sha];
+            for repo in &added.repos {
+                if let Some(key) = pending.remove(repo) {
+                    dates.insert(key, added.date.clone());
+                }
+            }
         }
 
+        Ok(dates)
+    }
+
+    /// Repositories linked on the lines a commit added to `path`
+    async fn fetch_commit_additions(&self, list: &GitHubListUrl, sha: &str, path: &str) -> Result<Vec<String>> {
+        let url = format!("https://api.github.com/repos/{}/{}/commits/{}", list.owner, list.repo, sha);
+
+        let response = self
+            .send(self.client.get(&url))
+            .await
+            .context("Failed to fetch commit")?;
+
         if !response.status().is_success() {
-            // If we can't fetch commits, just return empty - dates are optional
-            return Ok(dates);
+            anyhow::bail!("GitHub API error: {}", response.status());
         }
 
-        let commits: Vec<GitHubCommit> = response.json().await.context("Failed to parse commits")?;
+        let detail: GitHubCommitDetail = response.json().await.context("Failed to parse commit")?;
 
-        // For each repository, search through commits to find when it was added
-        for (repo_owner, repo_name) in repo_urls {
-            let search_string = format!("{}/{}", repo_owner, repo_name);
+        Ok(detail
+            .files
+            .iter()
+            .filter(|f| f.filename.eq_ignore_ascii_case(path))
+            .filter_map(|f| f.patch.as_deref())
+            .flat_map(added_repo_links)
+            .collect())
+    }
+}
 
-            // Search from oldest to newest (reverse order) to find first mention
-            for commit in commits.iter().rev() {
-                let commit_msg = commit.commit.message.to_lowercase();
-                let search_lower = search_string.to_lowercase();
+impl From<GraphQlRepo> for Repository {
+    fn from(repo: GraphQlRepo) -> Self {
+        Repository {
+            id: repo.database_id.unwrap_or(0),
+            name: repo.name,
+            full_name: repo.name_with_owner,
+            description: repo.description,
+            html_url: repo.url,
+            homepage: repo.homepage_url.filter(|h| !h.is_empty()),
+            stargazers_count: repo.stargazer_count,
+            forks_count: repo.fork_count,
+            // REST's open_issues_count includes open pull requests
+            open_issues_count: repo.issues.total_count + repo.pull_requests.total_count,
+            language: repo.primary_language.map(|l| l.name),
+            license: repo.license_info.map(|l| License {
+                key: l.key,
+                name: l.name,
+                spdx_id: l.spdx_id,
+                url: l.url,
+            }),
+            topics: repo.repository_topics.nodes.into_iter().map(|n| n.topic.name).collect(),
+            updated_at: repo.updated_at,
+            created_at: repo.created_at,
+            pushed_at: repo.pushed_at,
+            added_to_list_at: None,
+            archived: repo.is_archived,
+            disabled: repo.is_disabled,
+            fork: repo.is_fork,
+            is_template: repo.is_template,
+            linked_full_name: None,
+            latest_release: None,
+            release_count: None,
+            activity: None,
+        }
+    }
+}
 
-                // Check if this commit mentions the repository
-                if commit_msg.contains(&search_lower)
-                    || (commit_msg.contains("add") && commit_msg.contains(&repo_name.to_lowercase())) {
-                    let key = format!("{}/{}", repo_owner, repo_name);
-                    dates.insert(key, commit.commit.author.date.clone());
-                    break;
-                }
+impl RepositoryFetchResult {
+    pub fn from_result(host: &str, owner: &str, repo: &str, result: Result<Repository>) -> Self {
+        let (repository, error, unavailable) = match result {
+            Ok(repository) => (Some(with_linked_name(repository, owner, repo)), None, false),
+            Err(e) => {
+                let unavailable = e.downcast_ref::<RepositoryUnavailable>().is_some();
+                (None, Some(e.to_string()), unavailable)
             }
+        };
+        Self {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            repository,
+            error,
+            unavailable,
         }
+    }
+}
 
-        Ok(dates)
+/// Record the linked `owner/repo` when it no longer matches the canonical name.
+/// Links are case-insensitive, so a difference in case alone isn't stale.
+fn with_linked_name(mut repository: Repository, owner: &str, repo: &str) -> Repository {
+    let linked = format!("{}/{}", owner, repo);
+    if !repository.full_name.eq_ignore_ascii_case(&linked) {
+        repository.linked_full_name = Some(linked);
     }
+    repository
 }
 
-pub fn extract_repo_links(markdown: &str) -> Vec<(String, String)> {
-    extract_repo_links_with_categories(markdown)
-        .into_iter()
-        .map(|(owner, repo, _)| (owner, repo))
-        .collect()
+/// A GitHub API URL with every path segment and query value percent-encoded, so refs
+/// and file paths containing spaces, `#`, `+` or `&` reach the API intact. Path parts
+/// may contain several `/`-separated segments.
+fn api_url(path: &[&str], query: &[(&str, &str)]) -> String {
+    let mut url = reqwest::Url::parse("https://api.github.com").unwrap();
+    url.path_segments_mut()
+        .unwrap()
+        .extend(path.iter().flat_map(|part| part.split('/')).filter(|s| !s.is_empty()));
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+    url.into()
 }
 
-pub fn extract_repo_links_with_categories(markdown: &str) -> Vec<(String, String, String)> {
-    let mut repos = Vec::new();
-    let mut current_category = String::from("Uncategorized");
+/// Share of all commits made by the most active contributor, given each contributor's
+/// commit count; `None` without any commits
+fn top_contributor_share(commits: &[u32]) -> Option<f64> {
+    let total: u32 = commits.iter().sum();
+    let top = commits.iter().max()?;
+    (total > 0).then(|| *top as f64 / total as f64)
+}
+
+/// Page number of the `rel="last"` entry of a pagination `Link` header
+fn last_page_number(link: &str) -> Option<u32> {
+    link.split(',')
+        .find(|part| part.contains("rel=\"last\""))
+        .and_then(|part| part.split(['<', '>']).nth(1))
+        .and_then(|url| reqwest::Url::parse(url).ok())
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "page")
+                .and_then(|(_, page)| page.parse().ok())
+        })
+}
+
+/// A non-success status from the GitHub API
+#[derive(Debug)]
+pub struct GitHubApiError {
+    pub status: reqwest::StatusCode,
+}
+
+impl GitHubApiError {
+    /// Whether the status means the resource itself is gone rather than the request failing
+    pub fn is_gone(&self) -> bool {
+        matches!(self.status.as_u16(), 404 | 410 | 451)
+    }
+}
 
-    // Match GitHub repository URLs in markdown links
-    let link_pattern = Regex::new(r"https?://github\.com/([^/\s]+)/([^/\s)#]+)").unwrap();
-    // Match markdown headings (## or ###)
-    let heading_pattern = Regex::new(r"^#{2,3}\s+(.+)$").unwrap();
+impl fmt::Display for GitHubApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GitHub API error: {}", self.status)
+    }
+}
+
+impl std::error::Error for GitHubApiError {}
+
+/// A repository GitHub reports as deleted, private (to this token) or taken down
+#[derive(Debug)]
+pub struct RepositoryUnavailable {
+    pub full_name: String,
+    pub reason: String,
+}
 
-    for line in markdown.lines() {
-        // Check if this line is a heading
-        if let Some(cap) = heading_pattern.captures(line) {
-            current_category = cap.get(1).unwrap().as_str().trim().to_string();
+impl fmt::Display for RepositoryUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Repository {} is unavailable ({}): it was deleted, made private or taken down",
+            self.full_name, self.reason
+        )
+    }
+}
+
+impl std::error::Error for RepositoryUnavailable {}
+
+impl GitHubListUrl {
+    /// Path of the Markdown file within the repository, assuming `README.md` for directories
+    pub fn markdown_path(&self) -> String {
+        match (&self.path, self.path_is_file) {
+            (Some(path), true) => path.clone(),
+            (Some(dir), false) => format!("{}/README.md", dir.trim_end_matches('/')),
+            (None, _) => String::from("README.md"),
         }
+    }
+}
+
+/// Parse the GitHub URL of an awesome list.
+///
+/// Accepts repository URLs (with or without scheme, or plain `owner/repo`),
+/// `/tree/{ref}/{dir}` and `/blob/{ref}/{file}` URLs, and raw.githubusercontent.com
+/// file URLs. Refs containing `/` can't be told apart from the path and are read
+/// as their first segment.
+pub fn parse_github_url(url: &str) -> Result<GitHubListUrl> {
+    let trimmed = url.trim();
+    let without_fragment = trimmed.split(['#', '?']).next().unwrap_or("");
+    let without_scheme = without_fragment
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.");
+
+    let (raw, rest) = if let Some(rest) = without_scheme.strip_prefix("raw.githubusercontent.com/") {
+        (true, rest)
+    } else if let Some(rest) = without_scheme.strip_prefix("github.com/") {
+        (false, rest)
+    } else if without_scheme.split('/').next().unwrap_or("").contains('.') {
+        anyhow::bail!("Invalid GitHub URL: {}", url);
+    } else {
+        (false, without_scheme)
+    };
+
+    let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.len() < 2 {
+        anyhow::bail!("Invalid GitHub URL: {}", url);
+    }
 
-        // Extract repository links from this line
-        for cap in link_pattern.captures_iter(line) {
-            let owner = cap.get(1).unwrap().as_str().to_string();
-            let repo = cap.get(2).unwrap().as_str().to_string();
+    let owner = segments[0].to_string();
+    let repo = segments[1].trim_end_matches(".git").to_string();
 
-            // Filter out non-repository links
-            if owner != "sponsors" && !repo.contains("?") && !repo.contains("&") {
-                repos.push((owner, repo, current_category.clone()));
+    let (git_ref, path, path_is_file) = if raw {
+        let git_ref = segments.get(2).map(|s| s.to_string());
+        let path = segments.get(3..).filter(|p| !p.is_empty()).map(|p| p.join("/"));
+        (git_ref, path, true)
+    } else {
+        match segments.get(2) {
+            Some(&kind @ ("tree" | "blob")) => {
+                let git_ref = segments.get(3).map(|s| s.to_string());
+                let path = segments.get(4..).filter(|p| !p.is_empty()).map(|p| p.join("/"));
+                (git_ref, path, kind == "blob")
             }
+            _ => (None, None, false),
         }
-    }
+    };
 
-    // Remove duplicates while preserving order (keep first occurrence with its category)
-    let mut seen = std::collections::HashSet::new();
-    repos.retain(|(owner, repo, _)| {
-        let key = format!("{}/{}", owner, repo);
-        seen.insert(key)
-    });
+    Ok(GitHubListUrl {
+        owner,
+        repo,
+        git_ref,
+        path,
+        path_is_file,
+    })
+}
 
-    repos
+pub fn extract_repo_links(markdown: &str) -> Vec<(String, String)> {
+    extract_repo_links_with_categories(markdown)
+        .into_iter()
+        .map(|(owner, repo, _)| (owner, repo))
+        .collect()
+}
+
+pub fn extract_repo_links_with_categories(markdown: &str) -> Vec<(String, String, String)> {
+    parse_awesome_list(markdown, &[])
+        .into_iter()
+        .filter(|entry| entry.forge == Forge::GitHub)
+        .map(|entry| {
+            let category = entry_category(&entry);
+            (entry.owner, entry.repo, category)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -245,4 +1133,149 @@ https://github.com/user3/repo3
         assert_eq!(repos[1], ("user2".to_string(), "repo2".to_string()));
         assert_eq!(repos[2], ("user3".to_string(), "repo3".to_string()));
     }
+
+    #[test]
+    fn test_graphql_repository_mapping() {
+        let body = r#"{
+            "data": {
+                "r0": {
+                    "databaseId": 42, "name": "repo", "nameWithOwner": "org/repo",
+                    "description": null, "url": "https://github.com/org/repo", "homepageUrl": "",
+                    "stargazerCount": 10, "forkCount": 2,
+                    "issues": { "totalCount": 3 }, "pullRequests": { "totalCount": 1 },
+                    "primaryLanguage": { "name": "Rust" },
+                    "licenseInfo": { "key": "mit", "name": "MIT License", "spdxId": "MIT", "url": null },
+                    "repositoryTopics": { "nodes": [{ "topic": { "name": "cli" } }] },
+                    "updatedAt": "2024-01-01T00:00:00Z", "createdAt": "2020-01-01T00:00:00Z"
+                },
+                "r1": null
+            },
+            "errors": [{ "type": "NOT_FOUND", "path": ["r1"], "message": "Could not resolve to a Repository" }]
+        }"#;
+
+        let response: GraphQlResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(response.errors[0].path[0], "r1");
+
+        let mut data = response.data.unwrap();
+        assert!(data.remove("r1").unwrap().is_none());
+
+        let repository = Repository::from(data.remove("r0").unwrap().unwrap());
+        assert_eq!(repository.full_name, "org/repo");
+        assert_eq!(repository.open_issues_count, 4);
+        assert_eq!(repository.homepage, None);
+        assert_eq!(repository.topics, vec!["cli"]);
+    }
+
+    #[test]
+    fn test_parse_github_url() {
+        let list = parse_github_url("https://github.com/org/repo/blob/dev/docs/LIST.md").unwrap();
+        assert_eq!((list.owner.as_str(), list.repo.as_str()), ("org", "repo"));
+        assert_eq!(list.git_ref.as_deref(), Some("dev"));
+        assert_eq!(list.path.as_deref(), Some("docs/LIST.md"));
+        assert!(list.path_is_file);
+
+        let list = parse_github_url("https://github.com/org/repo/tree/v2").unwrap();
+        assert_eq!(list.git_ref.as_deref(), Some("v2"));
+        assert_eq!(list.path, None);
+        assert_eq!(list.markdown_path(), "README.md");
+
+        let list = parse_github_url("github.com/org/repo.git/").unwrap();
+        assert_eq!((list.owner.as_str(), list.repo.as_str(), list.git_ref), ("org", "repo", None));
+
+        let list = parse_github_url("https://raw.githubusercontent.com/org/repo/main/README.md").unwrap();
+        assert_eq!(list.git_ref.as_deref(), Some("main"));
+        assert_eq!(list.markdown_path(), "README.md");
+
+        assert!(parse_github_url("https://gitlab.com/org/repo").is_err());
+        assert!(parse_github_url("https://github.com/org").is_err());
+    }
+
+    #[test]
+    fn test_last_page_number() {
+        let link = "<https://api.github.com/repositories/1/releases?per_page=1&page=2>; rel=\"next\", \
+                    <https://api.github.com/repositories/1/releases?per_page=1&page=37>; rel=\"last\"";
+        assert_eq!(last_page_number(link), Some(37));
+
+        let link = "<https://api.github.com/repositories/1/releases?per_page=1&page=1>; rel=\"prev\"";
+        assert_eq!(last_page_number(link), None);
+    }
+
+    #[test]
+    fn test_api_url() {
+        assert_eq!(
+            api_url(&["repos", "org", "list", "contents", "/docs/My List#1.md"], &[("ref", "feature/a+b")]),
+            "https://api.github.com/repos/org/list/contents/docs/My%20List%231.md?ref=feature%2Fa%2Bb"
+        );
+        assert_eq!(api_url(&["repos", "org", "list", "readme", ""], &[]), "https://api.github.com/repos/org/list/readme");
+    }
+
+    #[test]
+    fn test_top_contributor_share() {
+        assert_eq!(top_contributor_share(&[30, 60, 10]), Some(0.6));
+        assert_eq!(top_contributor_share(&[5]), Some(1.0));
+        assert_eq!(top_contributor_share(&[0, 0]), None);
+        assert_eq!(top_contributor_share(&[]), None);
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_token() {
+        let url = "https://api.github.com/repos/org/private";
+        let key = |token: Option<&str>| {
+            GitHubClient::new(token.map(String::from), Arc::new(RateLimitTracker::default())).cache_key(url, None)
+        };
+
+        assert_eq!(key(None), format!("anonymous */* {}", url));
+        assert_eq!(key(Some("ghp_first")), key(Some("ghp_first")));
+        assert_ne!(key(Some("ghp_first")), key(None));
+        assert_ne!(key(Some("ghp_first")), key(Some("ghp_second")));
+        assert!(!key(Some("ghp_first")).contains("ghp_first"));
+    }
+
+    /// Serve one canned HTTP response per connection on a local port
+    async fn serve(responses: Vec<&'static str>) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/stats", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0; 4096];
+                let _ = socket.read(&mut request).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stats_retries_while_computing() {
+        const ACCEPTED: &str = "HTTP/1.1 202 Accepted\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}";
+        const READY: &str =
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 7\r\nConnection: close\r\n\r\n[1,2,3]";
+
+        let client = GitHubClient::new(None, Arc::new(RateLimitTracker::default()));
+        let url = serve(vec![ACCEPTED, READY]).await;
+        let stats: Option<Vec<u32>> = client.fetch_stats(&url).await.unwrap();
+        assert_eq!(stats, Some(vec![1, 2, 3]));
+
+        // Cancelling abandons the retry instead of sleeping through it
+        let cancelled = Arc::new(AtomicBool::new(true));
+        let client = client.with_cancel(cancelled);
+        let url = serve(vec![ACCEPTED]).await;
+        let started = std::time::Instant::now();
+        assert!(client.fetch_stats::<Vec<u32>>(&url).await.is_err());
+        assert!(started.elapsed() < STATS_RETRY_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_wait_stops_when_cancelled() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let client = GitHubClient::new(None, Arc::new(RateLimitTracker::default())).with_cancel(Arc::clone(&cancelled));
+        assert!(client.wait(Duration::from_millis(10)).await.is_ok());
+
+        cancelled.store(true, Ordering::SeqCst);
+        let waited = tokio::time::timeout(Duration::from_secs(5), client.wait(Duration::from_secs(3600))).await;
+        assert!(waited.expect("wait should end once cancelled").is_err());
+    }
 }
@@ -0,0 +1,263 @@
+use crate::models::github::{HealthComponent, HealthScore, HealthScoreResult, HealthSignals};
+use crate::services::github::GitHubClient;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Highest score an archived repository can reach, however active it was before
+const ARCHIVED_SCORE_CAP: u32 = 20;
+
+/// Score how actively a repository is maintained, from 0 to 100.
+///
+/// Each signal earns a share of its weight:
+///
+/// | Signal             | Weight | Full share                      | Partial shares                                  |
+/// |--------------------|--------|---------------------------------|-------------------------------------------------|
+/// | `last_push`        | 25     | pushed within 30 days           | 90 d: 0.8, 180 d: 0.6, 1 y: 0.3, 2 y: 0.1        |
+/// | `recent_commits`   | 20     | 30+ commits in 90 days          | 10+: 0.75, 3+: 0.5, 1+: 0.25                     |
+/// | `issue_resolution` | 15     | every issue closed              | closed / (open + closed); 0.5 with no issues     |
+/// | `release_cadence`  | 15     | released within 90 days         | 1 y: 0.7, 2 y: 0.3, older: 0.1, never: 0         |
+/// | `contributors`     | 10     | 20+ contributors, or too many   | 5+: 0.7, 2+: 0.4, 1: 0.1                         |
+/// |                    |        | for GitHub to list              |                                                 |
+/// | `ci`               | 10     | a GitHub Actions workflow       |                                                 |
+/// | `not_archived`     | 5      | not archived                    |                                                 |
+///
+/// Archived repositories are additionally capped at 20.
+pub fn compute_health(full_name: &str, signals: HealthSignals, now: DateTime<Utc>) -> HealthScore {
+    let mut components = Vec::new();
+    let mut add = |signal: &str, weight: u32, share: f64, detail: String| {
+        components.push(HealthComponent {
+            signal: signal.to_string(),
+            weight,
+            points: (weight as f64 * share).round() as u32,
+            detail,
+        });
+    };
+
+    match days_since(signals.pushed_at.as_deref(), now) {
+        Some(days) => add(
+            "last_push",
+            25,
+            tier(days, &[(30, 1.0), (90, 0.8), (180, 0.6), (365, 0.3), (730, 0.1)]),
+            format!("Last push {} days ago", days),
+        ),
+        None => add("last_push", 25, 0.0, String::from("No push recorded")),
+    }
+
+    let commits = signals.recent_commits;
+    let share = match commits {
+        30.. => 1.0,
+        10.. => 0.75,
+        3.. => 0.5,
+        1.. => 0.25,
+        0 => 0.0,
+    };
+    add("recent_commits", 20, share, format!("{} commits in the last 90 days", commits));
+
+    let issues = signals.open_issues + signals.closed_issues;
+    if issues == 0 {
+        add("issue_resolution", 15, 0.5, String::from("No issues filed"));
+    } else {
+        let closed = signals.closed_issues as f64 / issues as f64;
+        add(
+            "issue_resolution",
+            15,
+            closed,
+            format!("{}% of {} issues closed", (closed * 100.0).round(), issues),
+        );
+    }
+
+    match (signals.release_count, days_since(signals.latest_release_at.as_deref(), now)) {
+        (0, _) => add("release_cadence", 15, 0.0, String::from("No releases")),
+        (count, Some(days)) => add(
+            "release_cadence",
+            15,
+            tier(days, &[(90, 1.0), (365, 0.7), (730, 0.3)]).max(0.1),
+            format!("{} releases, latest {} days ago", count, days),
+        ),
+        (count, None) => add("release_cadence", 15, 0.1, format!("{} releases, none published", count)),
+    }
+
+    match signals.contributors {
+        Some(contributors) => {
+            let share = match contributors {
+                20.. => 1.0,
+                5.. => 0.7,
+                2.. => 0.4,
+                1 => 0.1,
+                0 => 0.0,
+            };
+            add("contributors", 10, share, format!("{} contributors", contributors));
+        }
+        // GitHub only refuses to list contributors for repositories with very large histories
+        None => add("contributors", 10, 1.0, String::from("Too many contributors for GitHub to list")),
+    }
+
+    if signals.has_ci {
+        add("ci", 10, 1.0, String::from("GitHub Actions workflows defined"));
+    } else {
+        add("ci", 10, 0.0, String::from("No GitHub Actions workflows"));
+    }
+
+    if signals.archived {
+        add("not_archived", 5, 0.0, String::from("Archived"));
+    } else {
+        add("not_archived", 5, 1.0, String::from("Not archived"));
+    }
+
+    let mut score: u32 = components.iter().map(|c| c.points).sum();
+    if signals.archived {
+        score = score.min(ARCHIVED_SCORE_CAP);
+    }
+
+    HealthScore {
+        full_name: full_name.to_string(),
+        score,
+        components,
+        signals,
+    }
+}
+
+/// Fetch signals for and score the given GitHub repositories with at most
+/// `concurrency` repositories in flight. Results are returned in request order.
+pub async fn score_repositories(
+    client: GitHubClient,
+    repos: Vec<(String, String)>,
+    concurrency: usize,
+) -> Vec<HealthScoreResult> {
+    let client = Arc::new(client);
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+    let mut tasks = JoinSet::new();
+    for (i, (owner, repo)) in repos.into_iter().enumerate() {
+        let client = Arc::clone(&client);
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let (health, error) = match client.fetch_health_signals(&owner, &repo).await {
+                Ok((repository, signals)) => (
+                    Some(compute_health(&repository.full_name, signals, Utc::now())),
+                    None,
+                ),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            (
+                i,
+                HealthScoreResult {
+                    owner,
+                    repo,
+                    health,
+                    error,
+                },
+            )
+        });
+    }
+
+    let mut results: Vec<(usize, HealthScoreResult)> = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn days_since(date: Option<&str>, now: DateTime<Utc>) -> Option<i64> {
+    let date = DateTime::parse_from_rfc3339(date?).ok()?;
+    Some((now - date.with_timezone(&Utc)).num_days().max(0))
+}
+
+/// Share for the first tier whose day limit `days` falls within, 0 past the last
+fn tier(days: i64, tiers: &[(i64, f64)]) -> f64 {
+    tiers
+        .iter()
+        .find(|(limit, _)| days <= *limit)
+        .map(|(_, share)| *share)
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signals() -> HealthSignals {
+        HealthSignals {
+            pushed_at: Some(String::from("2024-05-25T00:00:00Z")),
+            recent_commits: 42,
+            open_issues: 10,
+            closed_issues: 90,
+            release_count: 12,
+            latest_release_at: Some(String::from("2024-04-01T00:00:00Z")),
+            contributors: Some(35),
+            archived: false,
+            has_ci: true,
+        }
+    }
+
+    #[test]
+    fn test_compute_health() {
+        let now = DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().with_timezone(&Utc);
+
+        let healthy = compute_health("org/healthy", signals(), now);
+        let points: Vec<(&str, u32)> = healthy
+            .components
+            .iter()
+            .map(|c| (c.signal.as_str(), c.points))
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                ("last_push", 25),
+                ("recent_commits", 20),
+                ("issue_resolution", 14),
+                ("release_cadence", 15),
+                ("contributors", 10),
+                ("ci", 10),
+                ("not_archived", 5),
+            ]
+        );
+        assert_eq!(healthy.score, 99);
+        assert_eq!(healthy.components[2].detail, "90% of 100 issues closed");
+
+        let stale = compute_health(
+            "org/stale",
+            HealthSignals {
+                pushed_at: Some(String::from("2023-09-01T00:00:00Z")),
+                recent_commits: 0,
+                open_issues: 0,
+                closed_issues: 0,
+                release_count: 0,
+                latest_release_at: None,
+                contributors: Some(1),
+                has_ci: false,
+                ..signals()
+            },
+            now,
+        );
+        // 0.3 × 25 + 0.5 × 15 + 0.1 × 10 + 5
+        assert_eq!(stale.score, 8 + 8 + 1 + 5);
+
+        let archived = compute_health(
+            "org/archived",
+            HealthSignals {
+                archived: true,
+                ..signals()
+            },
+            now,
+        );
+        assert_eq!(archived.score, ARCHIVED_SCORE_CAP);
+
+        let huge = compute_health(
+            "org/huge",
+            HealthSignals {
+                contributors: None,
+                ..signals()
+            },
+            now,
+        );
+        assert_eq!(huge.score, healthy.score);
+        assert_eq!(huge.components[4].detail, "Too many contributors for GitHub to list");
+    }
+}
@@ -0,0 +1,355 @@
+use crate::models::github::{MetricPoint, Repository, RepositoryFetchResult, ScanResult, ScanSummary};
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Schema changes, applied in order; `PRAGMA user_version` records how many have run
+const MIGRATIONS: &[&str] = &["
+    CREATE TABLE scans (
+        scan_id TEXT PRIMARY KEY,
+        list_url TEXT NOT NULL,
+        scanned_at TEXT NOT NULL,
+        cancelled INTEGER NOT NULL,
+        failures TEXT NOT NULL
+    );
+    CREATE INDEX scans_by_list ON scans (list_url, scanned_at);
+    CREATE TABLE scan_repositories (
+        scan_id TEXT NOT NULL REFERENCES scans (scan_id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        full_name TEXT NOT NULL,
+        category TEXT,
+        repository TEXT NOT NULL,
+        PRIMARY KEY (scan_id, position)
+    );
+", "
+    CREATE TABLE metric_snapshots (
+        scan_id TEXT NOT NULL,
+        list_url TEXT NOT NULL,
+        scanned_at TEXT NOT NULL,
+        full_name TEXT NOT NULL,
+        stargazers_count INTEGER NOT NULL,
+        forks_count INTEGER NOT NULL,
+        open_issues_count INTEGER NOT NULL,
+        archived INTEGER NOT NULL,
+        PRIMARY KEY (scan_id, full_name)
+    );
+    CREATE INDEX metric_snapshots_by_list ON metric_snapshots (list_url, scanned_at);
+    INSERT OR IGNORE INTO metric_snapshots
+    SELECT r.scan_id, s.list_url, s.scanned_at, r.full_name,
+           json_extract(r.repository, '$.stargazers_count'),
+           json_extract(r.repository, '$.forks_count'),
+           json_extract(r.repository, '$.open_issues_count'),
+           COALESCE(json_extract(r.repository, '$.archived'), 0)
+    FROM scan_repositories r JOIN scans s ON s.scan_id = r.scan_id;
+", "
+    ALTER TABLE scans ADD COLUMN categories TEXT;
+"];
+
+/// Past scans stored in an SQLite database in the app data directory, each with a
+/// snapshot of every repository as it was fetched.
+///
+/// Each scan also records a small metric snapshot per repository. Those are kept
+/// when the scan itself is deleted, so trends keep their full history.
+///
+/// Commands can always reach it through state; until `open` succeeds every call fails.
+#[derive(Default)]
+pub struct ScanHistory {
+    conn: Mutex<Option<Connection>>,
+}
+
+impl ScanHistory {
+    pub fn open(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create database directory")?;
+        }
+        let mut conn = Connection::open(path).context("Failed to open scan history database")?;
+        conn.pragma_update(None, "foreign_keys", true)?;
+        migrate(&mut conn)?;
+        *self.conn.lock().unwrap() = Some(conn);
+        Ok(())
+    }
+
+    fn with_conn<T>(&self, f: impl FnOnce(&mut Connection) -> Result<T>) -> Result<T> {
+        let mut conn = self.conn.lock().unwrap();
+        let conn = conn.as_mut().context("Scan history database is not open")?;
+        f(conn)
+    }
+
+    /// Store a scan, replacing any earlier copy with the same ID
+    pub fn save_scan(&self, result: &ScanResult) -> Result<()> {
+        self.with_conn(|conn| {
+            let tx = conn.transaction()?;
+            tx.execute("DELETE FROM scans WHERE scan_id = ?1", params![result.scan_id])?;
+            tx.execute(
+                "INSERT INTO scans (scan_id, list_url, scanned_at, cancelled, failures, categories)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    result.scan_id,
+                    result.list_url,
+                    result.scanned_at,
+                    result.cancelled,
+                    serde_json::to_string(&result.failures)?,
+                    serde_json::to_string(&result.categories)?,
+                ],
+            )?;
+            {
+                let mut insert = tx.prepare(
+                    "INSERT INTO scan_repositories (scan_id, position, full_name, category, repository)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )?;
+                for (position, repository) in result.repositories.iter().enumerate() {
+                    insert.execute(params![
+                        result.scan_id,
+                        position as i64,
+                        repository.full_name,
+                        result.categories.get(&repository.category_key()),
+                        serde_json::to_string(repository)?,
+                    ])?;
+                }
+            }
+            tx.execute("DELETE FROM metric_snapshots WHERE scan_id = ?1", params![result.scan_id])?;
+            {
+                let mut insert = tx.prepare(
+                    "INSERT OR REPLACE INTO metric_snapshots
+                     (scan_id, list_url, scanned_at, full_name, stargazers_count, forks_count, open_issues_count, archived)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                )?;
+                for repository in &result.repositories {
+                    insert.execute(params![
+                        result.scan_id,
+                        result.list_url,
+                        result.scanned_at,
+                        repository.full_name,
+                        repository.stargazers_count,
+                        repository.forks_count,
+                        repository.open_issues_count,
+                        repository.archived,
+                    ])?;
+                }
+            }
+            tx.commit().context("Failed to save scan")
+        })
+    }
+
+    /// Stored scans, newest first, optionally only those of one list
+    pub fn list_scans(&self, list_url: Option<&str>) -> Result<Vec<ScanSummary>> {
+        self.with_conn(|conn| {
+            let mut query = conn.prepare(
+                "SELECT s.scan_id, s.list_url, s.scanned_at, s.cancelled,
+                        (SELECT COUNT(*) FROM scan_repositories r WHERE r.scan_id = s.scan_id),
+                        json_array_length(s.failures)
+                 FROM scans s
+                 WHERE ?1 IS NULL OR s.list_url = ?1
+                 ORDER BY s.scanned_at DESC",
+            )?;
+            let scans = query
+                .query_map(params![list_url], |row| {
+                    Ok(ScanSummary {
+                        scan_id: row.get(0)?,
+                        list_url: row.get(1)?,
+                        scanned_at: row.get(2)?,
+                        cancelled: row.get(3)?,
+                        repository_count: row.get(4)?,
+                        failure_count: row.get(5)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(scans)
+        })
+    }
+
+    pub fn load_scan(&self, scan_id: &str) -> Result<Option<ScanResult>> {
+        self.with_conn(|conn| {
+            let scan = conn
+                .query_row(
+                    "SELECT list_url, scanned_at, cancelled, failures, categories FROM scans WHERE scan_id = ?1",
+                    params![scan_id],
+                    |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, bool>(2)?,
+                            row.get::<_, String>(3)?,
+                            row.get::<_, Option<String>>(4)?,
+                        ))
+                    },
+                )
+                .optional()?;
+            let Some((list_url, scanned_at, cancelled, failures, stored_categories)) = scan else {
+                return Ok(None);
+            };
+
+            let mut query = conn.prepare(
+                "SELECT category, repository FROM scan_repositories
+                 WHERE scan_id = ?1 ORDER BY position",
+            )?;
+            let rows = query
+                .query_map(params![scan_id], |row| {
+                    Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            let mut repositories = Vec::with_capacity(rows.len());
+            let mut categories = HashMap::new();
+            for (category, repository) in rows {
+                let repository: Repository =
+                    serde_json::from_str(&repository).context("Failed to parse stored repository")?;
+                if let Some(category) = category {
+                    categories.insert(repository.category_key(), category);
+                }
+                repositories.push(repository);
+            }
+            let failures: Vec<RepositoryFetchResult> =
+                serde_json::from_str(&failures).context("Failed to parse stored failures")?;
+            // Scans saved before the whole map was stored only have their repositories' categories
+            if let Some(stored_categories) = stored_categories {
+                categories = serde_json::from_str(&stored_categories).context("Failed to parse stored categories")?;
+            }
+
+            Ok(Some(ScanResult {
+                scan_id: scan_id.to_string(),
+                list_url,
+                scanned_at,
+                cancelled,
+                repositories,
+                categories,
+                failures,
+            }))
+        })
+    }
+
+    /// Returns whether a scan with that ID existed
+    pub fn delete_scan(&self, scan_id: &str) -> Result<bool> {
+        self.with_conn(|conn| {
+            let deleted = conn.execute("DELETE FROM scans WHERE scan_id = ?1", params![scan_id])?;
+            Ok(deleted > 0)
+        })
+    }
+
+    /// Delete every scan taken before an RFC 3339 UTC timestamp, returning how many were removed
+    pub fn delete_scans_before(&self, before: &str) -> Result<usize> {
+        self.with_conn(|conn| {
+            let deleted = conn.execute("DELETE FROM scans WHERE scanned_at < ?1", params![before])?;
+            Ok(deleted)
+        })
+    }
+
+    /// Metric snapshots recorded by one scan, keyed by repository `full_name`
+    pub fn scan_metrics(&self, scan_id: &str) -> Result<Vec<(String, MetricPoint)>> {
+        self.query_metrics("WHERE scan_id = ?1", scan_id)
+    }
+
+    /// Metric snapshots recorded by every scan of a list, oldest scan first
+    pub fn list_metrics(&self, list_url: &str) -> Result<Vec<(String, MetricPoint)>> {
+        self.query_metrics("WHERE list_url = ?1", list_url)
+    }
+
+    fn query_metrics(&self, filter: &str, value: &str) -> Result<Vec<(String, MetricPoint)>> {
+        self.with_conn(|conn| {
+            let mut query = conn.prepare(&format!(
+                "SELECT full_name, scan_id, scanned_at, stargazers_count, forks_count, open_issues_count, archived
+                 FROM metric_snapshots {} ORDER BY scanned_at, full_name",
+                filter
+            ))?;
+            let metrics = query
+                .query_map(params![value], |row| {
+                    Ok((
+                        row.get(0)?,
+                        MetricPoint {
+                            scan_id: row.get(1)?,
+                            scanned_at: row.get(2)?,
+                            stargazers_count: row.get(3)?,
+                            forks_count: row.get(4)?,
+                            open_issues_count: row.get(5)?,
+                            archived: row.get(6)?,
+                        },
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(metrics)
+        })
+    }
+}
+
+fn migrate(conn: &mut Connection) -> Result<()> {
+    let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)
+            .with_context(|| format!("Failed to migrate scan history to version {}", i + 1))?;
+        tx.pragma_update(None, "user_version", i + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_history_round_trip() {
+        let history = ScanHistory::default();
+        assert!(history.list_scans(None).is_err());
+        history.open(Path::new(":memory:")).unwrap();
+
+        let mut categories = HashMap::new();
+        categories.insert(String::from("github.com/a/one"), String::from("Tools"));
+        categories.insert(String::from("github.com/c/gone"), String::from("Libraries"));
+        let scan = ScanResult {
+            scan_id: String::from("scan-1"),
+            list_url: String::from("https://github.com/a/awesome"),
+            scanned_at: String::from("2024-03-01T00:00:00+00:00"),
+            cancelled: false,
+            repositories: vec![
+                Repository { stargazers_count: 10, ..Repository::base("a/one") },
+                Repository { stargazers_count: 20, ..Repository::base("b/two") },
+            ],
+            categories,
+            failures: vec![RepositoryFetchResult::from_result(
+                "github.com",
+                "c",
+                "gone",
+                Err(anyhow::anyhow!("Repository not found")),
+            )],
+        };
+        history.save_scan(&scan).unwrap();
+        history
+            .save_scan(&ScanResult {
+                scan_id: String::from("scan-2"),
+                scanned_at: String::from("2024-03-02T00:00:00+00:00"),
+                ..scan.clone()
+            })
+            .unwrap();
+
+        let summaries = history.list_scans(Some("https://github.com/a/awesome")).unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].scan_id, "scan-2");
+        assert_eq!(summaries[1].repository_count, 2);
+        assert_eq!(summaries[1].failure_count, 1);
+
+        let loaded = history.load_scan("scan-1").unwrap().unwrap();
+        let names: Vec<&str> = loaded.repositories.iter().map(|r| r.full_name.as_str()).collect();
+        assert_eq!(names, vec!["a/one", "b/two"]);
+        assert_eq!(loaded.repositories[1].stargazers_count, 20);
+        assert_eq!(loaded.categories.get("github.com/a/one").map(String::as_str), Some("Tools"));
+        assert_eq!(loaded.failures[0].repo, "gone");
+        // Failed entries keep their category too
+        assert_eq!(loaded.categories.get("github.com/c/gone").map(String::as_str), Some("Libraries"));
+        assert!(history.load_scan("missing").unwrap().is_none());
+
+        assert_eq!(history.delete_scans_before("2024-03-02T00:00:00+00:00").unwrap(), 1);
+        assert!(history.delete_scan("scan-2").unwrap());
+        assert!(!history.delete_scan("scan-2").unwrap());
+        assert!(history.list_scans(None).unwrap().is_empty());
+
+        // Metric snapshots outlive the scans they came from
+        let metrics = history.list_metrics("https://github.com/a/awesome").unwrap();
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(metrics[0].0, "a/one");
+        assert_eq!(metrics[0].1.scan_id, "scan-1");
+        assert_eq!(history.scan_metrics("scan-2").unwrap()[1].1.stargazers_count, 20);
+    }
+}
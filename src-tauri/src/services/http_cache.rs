@@ -0,0 +1,159 @@
+use crate::services::rate_limit::unix_now;
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// A response body stored with the validators needed to revalidate it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub key: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+    /// Unix timestamp (seconds) of the last time the server confirmed this body
+    pub validated_at: u64,
+}
+
+impl CachedResponse {
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        unix_now().saturating_sub(self.validated_at) < ttl.as_secs()
+    }
+
+    /// Add `If-None-Match` / `If-Modified-Since` so an unchanged resource comes back as a 304
+    pub fn apply_validators(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
+/// On-disk cache of HTTP GET responses, one JSON file per request key.
+///
+/// The cache stays disabled until a directory is set, so clients can always be
+/// handed one. It is not tied to a particular API: callers choose the key and
+/// TTL per resource and decide when to revalidate.
+#[derive(Default)]
+pub struct HttpCache {
+    dir: RwLock<Option<PathBuf>>,
+}
+
+impl HttpCache {
+    pub fn set_dir(&self, dir: PathBuf) {
+        *self.dir.write().unwrap() = Some(dir);
+    }
+
+    pub fn get(&self, key: &str) -> Option<CachedResponse> {
+        let path = self.path_for(key)?;
+        let content = fs::read_to_string(path).ok()?;
+        let entry: CachedResponse = serde_json::from_str(&content).ok()?;
+        // Guard against hash collisions between keys
+        (entry.key == key).then_some(entry)
+    }
+
+    /// Store a successful response if it carries a validator; without one it could never be revalidated
+    pub fn put(&self, key: &str, headers: &HeaderMap, body: &str) {
+        let entry = CachedResponse {
+            key: key.to_string(),
+            etag: header_string(headers, ETAG),
+            last_modified: header_string(headers, LAST_MODIFIED),
+            body: body.to_string(),
+            validated_at: unix_now(),
+        };
+        if entry.etag.is_some() || entry.last_modified.is_some() {
+            self.write(&entry);
+        }
+    }
+
+    /// Mark a stored response as confirmed unchanged by a 304
+    pub fn touch(&self, mut entry: CachedResponse) -> CachedResponse {
+        entry.validated_at = unix_now();
+        self.write(&entry);
+        entry
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        if let Some(dir) = self.dir.read().unwrap().as_ref() {
+            if dir.exists() {
+                fs::remove_dir_all(dir).context("Failed to clear HTTP cache")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write(&self, entry: &CachedResponse) {
+        let Some(path) = self.path_for(&entry.key) else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(content) = serde_json::to_string(entry) {
+            let _ = fs::write(path, content);
+        }
+    }
+
+    fn path_for(&self, key: &str) -> Option<PathBuf> {
+        let dir = self.dir.read().unwrap().clone()?;
+        Some(dir.join(format!("{:016x}.json", fnv1a(key))))
+    }
+}
+
+fn header_string(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers.get(name).and_then(|v| v.to_str().ok()).map(String::from)
+}
+
+/// Tag identifying a credential in cache keys without storing the credential itself
+pub fn fingerprint(secret: &str) -> String {
+    format!("{:016x}", fnv1a(secret))
+}
+
+/// Stable 64-bit FNV-1a hash for cache file names
+pub(crate) fn fnv1a(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = std::env::temp_dir().join(format!("http-cache-test-{}", std::process::id()));
+        let cache = HttpCache::default();
+        let key = "application/json https://api.github.com/repos/owner/repo";
+
+        // Disabled until a directory is set
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        cache.put(key, &headers, "{}");
+        assert!(cache.get(key).is_none());
+
+        cache.set_dir(dir.clone());
+        cache.put(key, &headers, "{\"stars\": 1}");
+        let entry = cache.get(key).unwrap();
+        assert_eq!(entry.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(entry.body, "{\"stars\": 1}");
+        assert!(entry.is_fresh(Duration::from_secs(60)));
+        assert!(!entry.is_fresh(Duration::ZERO));
+
+        // Responses without validators are never stored
+        cache.put("other", &HeaderMap::new(), "body");
+        assert!(cache.get("other").is_none());
+
+        cache.clear().unwrap();
+        assert!(cache.get(key).is_none());
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,377 @@
+use crate::models::github::{ImportError, ImportedRepositories, License, Release, Repository, RepositoryActivity};
+use crate::services::export::{CategorizedRepository, ExportColumn};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+/// One record of a CSV file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRecord {
+    /// 1-based line the record starts on; quoted fields may span several lines
+    pub line: u32,
+    pub fields: Vec<String>,
+}
+
+/// Read RFC 4180 CSV: fields optionally quoted, `""` for an embedded quote, and
+/// LF or CRLF line endings. A leading byte order mark and blank lines are skipped.
+pub fn read_csv(text: &str, delimiter: char) -> Result<Vec<CsvRecord>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    let mut line = 1;
+    let mut start = 1;
+
+    let mut chars = text.strip_prefix('\u{feff}').unwrap_or(text).chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c == delimiter && !quoted => {
+                fields.push(std::mem::take(&mut field));
+                started = true;
+            }
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                if started {
+                    fields.push(std::mem::take(&mut field));
+                    records.push(CsvRecord {
+                        line: start,
+                        fields: std::mem::take(&mut fields),
+                    });
+                }
+                line += 1;
+                start = line;
+                started = false;
+            }
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+                started = true;
+            }
+        }
+    }
+
+    if quoted {
+        anyhow::bail!("Line {}: quoted field is never closed", start);
+    }
+    if started {
+        fields.push(field);
+        records.push(CsvRecord { line: start, fields });
+    }
+    Ok(records)
+}
+
+/// Read a JSON export: an array of repositories, each optionally with a `category`
+pub fn import_json(text: &str) -> Result<ImportedRepositories> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_str(text).context("Expected a JSON array of repositories")?;
+
+    let mut importer = Importer::default();
+    for (i, value) in values.into_iter().enumerate() {
+        importer.add(i as u32 + 1, serde_json::from_value(value).map_err(|e| e.to_string()));
+    }
+    Ok(importer.finish())
+}
+
+/// Read an NDJSON export: one repository per line, each optionally with a `category`
+pub fn import_ndjson(text: &str) -> ImportedRepositories {
+    let mut importer = Importer::default();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        importer.add(i as u32 + 1, serde_json::from_str(line).map_err(|e| e.to_string()));
+    }
+    importer.finish()
+}
+
+/// Read a CSV export. Columns are matched by their header, in any order; unknown
+/// columns are ignored and missing ones left empty, but a `Full Name` or `URL` column
+/// is required. CSV doesn't carry every field, so imported repositories have no ID,
+/// only a license name and at most a contributor count for activity.
+pub fn import_csv(text: &str, delimiter: char, topic_separator: &str) -> Result<ImportedRepositories> {
+    let records = read_csv(text, delimiter)?;
+    let Some((header, rows)) = records.split_first() else {
+        anyhow::bail!("The CSV file is empty");
+    };
+
+    let columns: Vec<Option<ExportColumn>> = header.fields.iter().map(|h| ExportColumn::from_header(h)).collect();
+    if !columns
+        .iter()
+        .any(|c| matches!(c, Some(ExportColumn::FullName | ExportColumn::Url)))
+    {
+        anyhow::bail!("The CSV header has neither a \"Full Name\" nor a \"URL\" column");
+    }
+
+    let mut importer = Importer::default();
+    for row in rows {
+        let record = if row.fields.len() == columns.len() {
+            csv_repository(&columns, &row.fields, topic_separator)
+        } else {
+            Err(format!("Expected {} fields, found {}", columns.len(), row.fields.len()))
+        };
+        importer.add(row.line, record);
+    }
+    Ok(importer.finish())
+}
+
+fn csv_repository(
+    columns: &[Option<ExportColumn>],
+    fields: &[String],
+    topic_separator: &str,
+) -> Result<CategorizedRepository, String> {
+    let cells: HashMap<ExportColumn, &str> = columns
+        .iter()
+        .zip(fields)
+        .filter_map(|(column, field)| Some(((*column)?, field.trim())))
+        .filter(|(_, field)| !field.is_empty())
+        .collect();
+    let text = |column: ExportColumn| cells.get(&column).map(|value| value.to_string());
+    let number = |column: ExportColumn| -> Result<Option<u32>, String> {
+        cells
+            .get(&column)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("{}: \"{}\" is not a whole number", column.header(), value))
+            })
+            .transpose()
+    };
+
+    let html_url = text(ExportColumn::Url);
+    let full_name = match (text(ExportColumn::FullName), &html_url) {
+        (Some(full_name), _) => full_name,
+        (None, Some(url)) => {
+            let mut segments = url.trim_end_matches('/').rsplit('/');
+            let repo = segments.next().unwrap_or_default();
+            let owner = segments.next().unwrap_or_default();
+            format!("{}/{}", owner, repo)
+        }
+        (None, None) => return Err(String::from("Neither a full name nor a URL is given")),
+    };
+    let name = text(ExportColumn::Name)
+        .or_else(|| full_name.split_once('/').map(|(_, repo)| repo.to_string()))
+        .unwrap_or_default();
+
+    let repository = Repository {
+        id: 0,
+        name,
+        html_url: html_url.unwrap_or_else(|| format!("https://github.com/{}", full_name)),
+        full_name,
+        description: text(ExportColumn::Description),
+        homepage: text(ExportColumn::Homepage),
+        stargazers_count: number(ExportColumn::Stars)?.unwrap_or(0),
+        forks_count: number(ExportColumn::Forks)?.unwrap_or(0),
+        open_issues_count: number(ExportColumn::OpenIssues)?.unwrap_or(0),
+        language: text(ExportColumn::Language),
+        license: text(ExportColumn::License).map(|name| License {
+            key: name.to_lowercase(),
+            name,
+            spdx_id: None,
+            url: None,
+        }),
+        topics: text(ExportColumn::Topics)
+            .map(|topics| {
+                topics
+                    .split(topic_separator)
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        updated_at: text(ExportColumn::UpdatedAt).unwrap_or_default(),
+        created_at: text(ExportColumn::CreatedAt).unwrap_or_default(),
+        pushed_at: None,
+        added_to_list_at: text(ExportColumn::AddedToList),
+        archived: false,
+        disabled: false,
+        fork: false,
+        is_template: false,
+        linked_full_name: None,
+        latest_release: text(ExportColumn::LatestRelease).map(|tag_name| Release {
+            tag_name,
+            name: None,
+            published_at: text(ExportColumn::ReleaseDate),
+            prerelease: false,
+        }),
+        release_count: number(ExportColumn::Releases)?,
+        activity: number(ExportColumn::Contributors)?.map(|count| RepositoryActivity {
+            contributor_count: Some(count),
+            top_contributor_share: None,
+            weekly_commits: None,
+            languages: Vec::new(),
+        }),
+    };
+
+    Ok(CategorizedRepository {
+        repository,
+        category: text(ExportColumn::Category),
+    })
+}
+
+/// Collects valid records and per-row errors, rejecting repeated repositories
+#[derive(Default)]
+struct Importer {
+    imported: ImportedRepositories,
+    /// Row each lowercase `full_name` was first imported from
+    rows: HashMap<String, u32>,
+}
+
+impl Importer {
+    fn add(&mut self, row: u32, record: Result<CategorizedRepository, String>) {
+        let result = record.and_then(|record| {
+            validate(&record.repository)?;
+            let key = record.repository.full_name.to_lowercase();
+            if let Some(first) = self.rows.get(&key) {
+                return Err(format!("{} was already imported from row {}", record.repository.full_name, first));
+            }
+            self.rows.insert(key, row);
+            Ok(record)
+        });
+
+        match result {
+            Ok(record) => {
+                if let Some(category) = record.category {
+                    self.imported
+                        .categories
+                        .insert(record.repository.full_name.clone(), category);
+                }
+                self.imported.repositories.push(record.repository);
+            }
+            Err(message) => self.imported.errors.push(ImportError { row, message }),
+        }
+    }
+
+    fn finish(self) -> ImportedRepositories {
+        self.imported
+    }
+}
+
+fn validate(repository: &Repository) -> Result<(), String> {
+    match repository.full_name.split_once('/') {
+        Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() => {}
+        _ => return Err(format!("\"{}\" is not an owner/repo name", repository.full_name)),
+    }
+    if repository.name.is_empty() {
+        return Err(String::from("Name is empty"));
+    }
+    if !repository.html_url.starts_with("http://") && !repository.html_url.starts_with("https://") {
+        return Err(format!("\"{}\" is not an http(s) URL", repository.html_url));
+    }
+
+    let dates = [
+        ("created_at", Some(&repository.created_at)),
+        ("updated_at", Some(&repository.updated_at)),
+        ("pushed_at", repository.pushed_at.as_ref()),
+        ("added_to_list_at", repository.added_to_list_at.as_ref()),
+    ];
+    for (field, date) in dates {
+        if let Some(date) = date.filter(|d| !d.is_empty()) {
+            if chrono::DateTime::parse_from_rfc3339(date).is_err() {
+                return Err(format!("{}: \"{}\" is not an RFC 3339 date", field, date));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_csv() {
+        let records = read_csv("\u{feff}a,b\r\n\"x, \"\"y\"\"\",\"two\nlines\"\r\n\r\nlast,\n", ',').unwrap();
+        assert_eq!(
+            records,
+            vec![
+                CsvRecord {
+                    line: 1,
+                    fields: vec![String::from("a"), String::from("b")],
+                },
+                CsvRecord {
+                    line: 2,
+                    fields: vec![String::from("x, \"y\""), String::from("two\nlines")],
+                },
+                CsvRecord {
+                    line: 5,
+                    fields: vec![String::from("last"), String::new()],
+                },
+            ]
+        );
+        assert!(read_csv("a,\"open\n", ',').is_err());
+    }
+
+    #[test]
+    fn test_import_csv() {
+        let csv = "Full Name,Stars,Topics,Category,Unknown,Updated At\n\
+                   org/tool,12,cli; terminal,Tools,ignored,2024-01-01T00:00:00Z\n\
+                   org/bad,many,,,,\n\
+                   Org/Tool,1,,,,\n\
+                   org/short,3\n\
+                   org/undated,4,,,,yesterday\n\
+                   ,,,,,\n";
+
+        let imported = import_csv(csv, ',', ";").unwrap();
+        assert_eq!(imported.repositories.len(), 1);
+        let tool = &imported.repositories[0];
+        assert_eq!((tool.name.as_str(), tool.stargazers_count), ("tool", 12));
+        assert_eq!(tool.html_url, "https://github.com/org/tool");
+        assert_eq!(tool.topics, vec!["cli", "terminal"]);
+        assert_eq!(imported.categories["org/tool"], "Tools");
+
+        let errors: Vec<(u32, &str)> = imported.errors.iter().map(|e| (e.row, e.message.as_str())).collect();
+        assert_eq!(
+            errors,
+            vec![
+                (3, "Stars: \"many\" is not a whole number"),
+                (4, "Org/Tool was already imported from row 2"),
+                (5, "Expected 6 fields, found 2"),
+                (6, "updated_at: \"yesterday\" is not an RFC 3339 date"),
+                (7, "Neither a full name nor a URL is given"),
+            ]
+        );
+
+        assert!(import_csv("Name,Stars\ntool,1\n", ',', ";").is_err());
+    }
+
+    #[test]
+    fn test_import_json_and_ndjson() {
+        let json = r#"[
+            {"id": 1, "name": "tool", "full_name": "org/tool", "description": null,
+             "html_url": "https://github.com/org/tool", "homepage": null, "stargazers_count": 5,
+             "forks_count": 0, "open_issues_count": 0, "language": null, "license": null, "topics": [],
+             "updated_at": "2024-01-01T00:00:00Z", "created_at": "2020-01-01T00:00:00Z",
+             "added_to_list_at": null, "category": "Tools"},
+            {"id": 2, "name": "broken"}
+        ]"#;
+        let imported = import_json(json).unwrap();
+        assert_eq!(imported.repositories[0].full_name, "org/tool");
+        assert_eq!(imported.categories["org/tool"], "Tools");
+        assert_eq!(imported.errors.len(), 1);
+        assert_eq!(imported.errors[0].row, 2);
+        assert!(imported.errors[0].message.contains("full_name"));
+        assert!(import_json("{}").is_err());
+
+        let ndjson = format!("{}\n\nnot json\n", serde_json::to_string(&serde_json::json!({
+            "id": 1, "name": "tool", "full_name": "org/tool", "description": null,
+            "html_url": "https://github.com/org/tool", "homepage": null, "stargazers_count": 5,
+            "forks_count": 0, "open_issues_count": 0, "language": null, "license": null, "topics": [],
+            "updated_at": "", "created_at": "", "added_to_list_at": null
+        })).unwrap());
+        let imported = import_ndjson(&ndjson);
+        assert_eq!(imported.repositories.len(), 1);
+        assert!(imported.categories.is_empty());
+        assert_eq!(imported.errors[0].row, 3);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::models::github::{LinkCheckResult, LinkStatus, Repository};
+use crate::services::markdown::{parse_list, LineIndex};
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Links checked at once when the caller doesn't choose
+pub const DEFAULT_LINK_CONCURRENCY: usize = 8;
+
+/// Minimum gap between two requests to the same host
+pub const HOST_DELAY: Duration = Duration::from_millis(500);
+
+const MAX_REDIRECTS: usize = 10;
+
+const LINK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A distinct URL to check, with everywhere it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkTarget {
+    pub url: String,
+    pub sections: Vec<String>,
+    pub line: Option<u32>,
+    pub repositories: Vec<String>,
+}
+
+/// Gather every http(s) URL in a README plus the homepages of the given repositories,
+/// one target per URL (ignoring `#fragments`), in order of first appearance
+pub fn collect_link_targets(markdown: &str, repositories: &[Repository]) -> Vec<LinkTarget> {
+    let parsed = parse_list(markdown, &[]);
+    let lines = LineIndex::new(markdown);
+
+    let mut targets: Vec<LinkTarget> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut target_for = |url: &str, targets: &mut Vec<LinkTarget>| -> usize {
+        let url = url.split('#').next().unwrap_or(url).to_string();
+        *index.entry(url.clone()).or_insert_with(|| {
+            targets.push(LinkTarget {
+                url,
+                sections: Vec::new(),
+                line: None,
+                repositories: Vec::new(),
+            });
+            targets.len() - 1
+        })
+    };
+
+    for link in &parsed.links {
+        let i = target_for(&link.url, &mut targets);
+        let section = link
+            .section
+            .map(|s| parsed.sections[s].title.clone())
+            .unwrap_or_else(|| String::from("Uncategorized"));
+        let target = &mut targets[i];
+        if !target.sections.contains(&section) {
+            target.sections.push(section);
+        }
+        if target.line.is_none() {
+            target.line = Some(lines.position(markdown, link.offset).0);
+        }
+    }
+
+    for repository in repositories {
+        let Some(homepage) = repository.homepage.as_deref().map(str::trim) else {
+            continue;
+        };
+        if !homepage.starts_with("http://") && !homepage.starts_with("https://") {
+            continue;
+        }
+        let i = target_for(homepage, &mut targets);
+        targets[i].repositories.push(repository.full_name.clone());
+    }
+
+    targets
+}
+
+/// Check every target with at most `concurrency` requests in flight and at most one
+/// request per `host_delay` to any single host. Each URL is tried with HEAD first and
+/// GET when HEAD fails or is refused; redirects are followed by hand so each hop is
+/// recorded. Results are returned in target order.
+pub async fn check_links(targets: Vec<LinkTarget>, concurrency: usize, host_delay: Duration) -> Vec<LinkCheckResult> {
+    let client = reqwest::Client::builder()
+        .user_agent("developer-dashboard/0.1.0")
+        .redirect(Policy::none())
+        .timeout(LINK_TIMEOUT)
+        .build()
+        .unwrap();
+    let throttle = Arc::new(HostThrottle::new(host_delay));
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+    let mut tasks = JoinSet::new();
+    for (i, target) in targets.into_iter().enumerate() {
+        let client = client.clone();
+        let throttle = Arc::clone(&throttle);
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let outcome = check_link(&client, &throttle, &target.url).await;
+            (
+                i,
+                LinkCheckResult {
+                    url: target.url,
+                    sections: target.sections,
+                    line: target.line,
+                    repositories: target.repositories,
+                    status: outcome.status,
+                    http_status: outcome.http_status,
+                    redirects: outcome.redirects,
+                    error: outcome.error,
+                },
+            )
+        });
+    }
+
+    let mut results: Vec<(usize, LinkCheckResult)> = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+struct LinkOutcome {
+    status: LinkStatus,
+    http_status: Option<u16>,
+    redirects: Vec<String>,
+    error: Option<String>,
+}
+
+async fn check_link(client: &reqwest::Client, throttle: &HostThrottle, url: &str) -> LinkOutcome {
+    let mut redirects = Vec::new();
+    let failed = |redirects: Vec<String>, error: String| LinkOutcome {
+        status: LinkStatus::Error,
+        http_status: None,
+        redirects,
+        error: Some(error),
+    };
+
+    let mut current = match Url::parse(url) {
+        Ok(url) => url,
+        Err(e) => return failed(redirects, format!("Invalid URL: {}", e)),
+    };
+
+    loop {
+        let response = match request(client, throttle, &current).await {
+            Ok(response) => response,
+            Err(e) => return failed(redirects, e.to_string()),
+        };
+        let status = response.status();
+
+        if status.is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .and_then(|l| current.join(l).ok());
+            let Some(next) = location else {
+                return failed(redirects, format!("{} without a valid Location header", status));
+            };
+            if redirects.len() >= MAX_REDIRECTS {
+                return failed(redirects, String::from("Too many redirects"));
+            }
+            redirects.push(next.to_string());
+            current = next;
+            continue;
+        }
+
+        let link_status = match (status.is_success(), redirects.is_empty()) {
+            (true, true) => LinkStatus::Ok,
+            (true, false) => LinkStatus::Redirected,
+            (false, _) => LinkStatus::Broken,
+        };
+        return LinkOutcome {
+            status: link_status,
+            http_status: Some(status.as_u16()),
+            redirects,
+            error: None,
+        };
+    }
+}
+
+/// HEAD first; many servers reject or mishandle it, so fall back to GET on any failure
+async fn request(client: &reqwest::Client, throttle: &HostThrottle, url: &Url) -> reqwest::Result<reqwest::Response> {
+    throttle.wait(url).await;
+    if let Ok(response) = client.head(url.clone()).send().await {
+        let status = response.status();
+        if status.is_success() || status.is_redirection() {
+            return Ok(response);
+        }
+    }
+
+    throttle.wait(url).await;
+    client.get(url.clone()).send().await
+}
+
+/// Spaces out requests to the same host
+struct HostThrottle {
+    delay: Duration,
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostThrottle {
+    fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            next: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn wait(&self, url: &Url) {
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        let wait = {
+            let mut next = self.next.lock().unwrap();
+            let now = Instant::now();
+            let slot = next.get(&host).copied().filter(|t| *t > now).unwrap_or(now);
+            next.insert(host, slot + self.delay);
+            slot - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_link_targets() {
+        let markdown = r#"# Awesome Things
+
+- [Guide](https://example.com/guide#intro) - Read this first.
+
+## Articles
+
+- [Guide again](https://example.com/guide) - Same page.
+- [Post](https://blog.example.org/post) - Plain https://docs.example.net/page link.
+
+```
+https://example.com/in-code-block
+```
+"#;
+        let repository = Repository {
+            homepage: Some(String::from("https://blog.example.org/post")),
+            ..Repository::base("org/tool")
+        };
+
+        let targets = collect_link_targets(markdown, &[repository]);
+        let urls: Vec<&str> = targets.iter().map(|t| t.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/guide",
+                "https://blog.example.org/post",
+                "https://docs.example.net/page",
+            ]
+        );
+        assert_eq!(targets[0].sections, vec![String::from("Uncategorized"), String::from("Articles")]);
+        assert_eq!(targets[0].line, Some(3));
+        assert_eq!(targets[1].repositories, vec![String::from("org/tool")]);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::models::github::{Forge, LintDiagnostic, LintSeverity};
+use crate::services::markdown::{parse_list, LineIndex, ParsedList};
+use std::collections::HashSet;
+
+/// Check an awesome list README the way awesome-lint does: items sorted within
+/// their section, no repository listed twice, every item described with a
+/// capitalised sentence whatever site it links to, in-page links pointing at real headings, and no links to
+/// archived repositories.
+///
+/// `archived` holds the lowercase `owner/repo` of GitHub repositories known to be
+/// archived, typically taken from a scan of the list. Diagnostics are returned in
+/// source order.
+pub fn lint_awesome_list(markdown: &str, gitea_hosts: &[String], archived: &HashSet<String>) -> Vec<LintDiagnostic> {
+    let parsed = parse_list(markdown, gitea_hosts);
+    let lines = LineIndex::new(markdown);
+    let mut diagnostics: Vec<(usize, LintDiagnostic)> = Vec::new();
+    let mut report = |offset: usize, rule: &str, severity: LintSeverity, message: String| {
+        let (line, column) = lines.position(markdown, offset);
+        diagnostics.push((
+            offset,
+            LintDiagnostic {
+                rule: rule.to_string(),
+                severity,
+                message,
+                line,
+                column,
+            },
+        ));
+    };
+
+    check_order(&parsed, &mut report);
+
+    for duplicate in &parsed.duplicates {
+        let first = &parsed.entries[duplicate.first];
+        let first_section = parsed.entry_sections[duplicate.first];
+        let message = if duplicate.section == first_section {
+            format!("{}/{} is listed twice in this section", first.owner, first.repo)
+        } else {
+            format!(
+                "{}/{} is already listed under \"{}\"",
+                first.owner,
+                first.repo,
+                section_title(&parsed, first_section)
+            )
+        };
+        report(duplicate.offset, "duplicate-entry", LintSeverity::Error, message);
+    }
+
+    for item in &parsed.items {
+        match item.description.as_deref() {
+            None => report(
+                item.offset,
+                "missing-description",
+                LintSeverity::Warning,
+                format!("\"{}\" has no description", item.name),
+            ),
+            Some(description) => {
+                if description.chars().next().is_some_and(|c| c.is_lowercase()) {
+                    report(
+                        item.offset,
+                        "description-case",
+                        LintSeverity::Warning,
+                        format!("Description of \"{}\" should start with an uppercase letter", item.name),
+                    );
+                }
+                if !description.ends_with(['.', '!', '?']) {
+                    report(
+                        item.offset,
+                        "description-punctuation",
+                        LintSeverity::Warning,
+                        format!("Description of \"{}\" should end with a period", item.name),
+                    );
+                }
+            }
+        }
+    }
+
+    for (i, entry) in parsed.entries.iter().enumerate() {
+        let offset = parsed.entry_offsets[i];
+        let full_name = format!("{}/{}", entry.owner, entry.repo).to_lowercase();
+        if entry.forge == Forge::GitHub && archived.contains(&full_name) {
+            report(
+                offset,
+                "archived-repository",
+                LintSeverity::Warning,
+                format!("{}/{} is archived", entry.owner, entry.repo),
+            );
+        }
+    }
+
+    for (fragment, offset) in &parsed.anchor_links {
+        if !parsed.heading_anchors.contains(&fragment.to_lowercase()) {
+            report(
+                *offset,
+                "broken-anchor",
+                LintSeverity::Error,
+                format!("No heading has the anchor #{}", fragment),
+            );
+        }
+    }
+
+    diagnostics.sort_by_key(|(offset, _)| *offset);
+    diagnostics.into_iter().map(|(_, diagnostic)| diagnostic).collect()
+}
+
+/// Flag each item that sorts before the item above it in the same section
+fn check_order(parsed: &ParsedList, report: &mut impl FnMut(usize, &str, LintSeverity, String)) {
+    let mut previous: Option<(Option<usize>, String, &str)> = None;
+    for item in &parsed.items {
+        if item.name.is_empty() {
+            continue;
+        }
+        let key = item.name.to_lowercase();
+        if let Some((previous_section, previous_key, previous_name)) = &previous {
+            if *previous_section == item.section && key < *previous_key {
+                report(
+                    item.offset,
+                    "alphabetical-order",
+                    LintSeverity::Warning,
+                    format!("\"{}\" should come before \"{}\"", item.name, previous_name),
+                );
+            }
+        }
+        previous = Some((item.section, key, &item.name));
+    }
+}
+
+fn section_title(parsed: &ParsedList, section: Option<usize>) -> &str {
+    section
+        .map(|i| parsed.sections[i].title.as_str())
+        .unwrap_or("Uncategorized")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_awesome_list() {
+        let markdown = "# Awesome Things
+
+## Contents
+
+- [Databases](#databases)
+- [Tools](#tooling)
+
+## Databases
+
+- [Beta](https://github.com/org/beta) - Column store.
+- [Alpha](https://github.com/org/alpha) - key-value store
+- [Gamma](https://github.com/org/gamma)
+- [Hosted DB](https://hosted.example.com) - hosted database
+- [Cloud](https://cloud.example.com)
+
+## Tools
+
+- [Alpha again](https://github.com/org/alpha) - Same repository.
+- [Old](https://github.com/org/old) - Unmaintained tool.
+";
+        let archived: HashSet<String> = [String::from("org/old")].into_iter().collect();
+
+        let diagnostics = lint_awesome_list(markdown, &[], &archived);
+        let summary: Vec<(&str, u32, u32)> = diagnostics
+            .iter()
+            .map(|d| (d.rule.as_str(), d.line, d.column))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("broken-anchor", 6, 3),
+                ("alphabetical-order", 11, 3),
+                ("description-case", 11, 3),
+                ("description-punctuation", 11, 3),
+                ("missing-description", 12, 3),
+                ("description-case", 13, 3),
+                ("description-punctuation", 13, 3),
+                ("alphabetical-order", 14, 3),
+                ("missing-description", 14, 3),
+                ("duplicate-entry", 18, 3),
+                ("archived-repository", 19, 3),
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, LintSeverity::Error);
+        assert_eq!(diagnostics[1].message, "\"Alpha\" should come before \"Beta\"");
+        assert_eq!(diagnostics[7].message, "\"Cloud\" should come before \"Hosted DB\"");
+        assert_eq!(diagnostics[9].message, "org/alpha is already listed under \"Databases\"");
+    }
+}
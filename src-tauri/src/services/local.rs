@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// README names looked for at the root of a directory, ignoring case, in order of preference.
+/// Lists are parsed as Markdown, so READMEs in other formats aren't picked up.
+const README_NAMES: &[&str] = &["readme.md", "readme.markdown"];
+
+/// Read an awesome list from disk.
+///
+/// `path` may point at a Markdown file directly, or at a directory such as a git
+/// working copy, in which case its root README is used.
+pub fn read_local_readme(path: &Path) -> Result<String> {
+    let file = resolve_readme(path)?;
+    fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))
+}
+
+fn resolve_readme(path: &Path) -> Result<PathBuf> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    if !path.is_dir() {
+        anyhow::bail!("Path does not exist: {}", path.display());
+    }
+
+    let entries = fs::read_dir(path).with_context(|| format!("Failed to read directory {}", path.display()))?;
+    let mut others = Vec::new();
+    let mut readme: Option<((usize, String), PathBuf)> = None;
+    for entry in entries.flatten().filter(|entry| entry.path().is_file()) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let lower = name.to_lowercase();
+        match README_NAMES.iter().position(|n| *n == lower) {
+            // Ties such as README.md next to readme.md are broken by name, so the
+            // choice doesn't depend on directory order
+            Some(rank) => {
+                let key = (rank, name);
+                if readme.as_ref().is_none_or(|(best, _)| key < *best) {
+                    readme = Some((key, entry.path()));
+                }
+            }
+            None if lower.starts_with("readme") => others.push(name),
+            None => {}
+        }
+    }
+
+    if let Some((_, readme)) = readme {
+        return Ok(readme);
+    }
+
+    if others.is_empty() {
+        anyhow::bail!("No README.md or README.markdown found in {}", path.display())
+    }
+    others.sort();
+    anyhow::bail!(
+        "No README.md or README.markdown found in {} (found {})",
+        path.display(),
+        others.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str, files: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("local-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for file in files {
+            fs::write(dir.join(file), *file).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_resolve_readme() {
+        let dir = temp_dir("preferred", &["README.txt", "readme.md", "CONTRIBUTING.md"]);
+        assert_eq!(read_local_readme(&dir).unwrap(), "readme.md");
+        // A file path is read as is
+        assert_eq!(read_local_readme(&dir.join("README.txt")).unwrap(), "README.txt");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let dir = temp_dir("markdown", &["README", "ReadMe.MARKDOWN"]);
+        assert_eq!(read_local_readme(&dir).unwrap(), "ReadMe.MARKDOWN");
+        fs::remove_dir_all(&dir).unwrap();
+
+        // READMEs in other formats aren't read as Markdown, but the error names them
+        let dir = temp_dir("other-formats", &["readme.txt", "README.rst"]);
+        let error = read_local_readme(&dir).unwrap_err().to_string();
+        assert!(error.ends_with("(found README.rst, readme.txt)"), "{}", error);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let dir = temp_dir("missing", &["index.md"]);
+        assert!(read_local_readme(&dir).is_err());
+        assert!(read_local_readme(&dir.join("nested")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+use crate::models::github::{MaintenanceAction, MaintenanceIssue, MaintenanceIssueKind, MaintenanceReport, ScanResult};
+
+/// List the entries of a scan whose links should be updated or removed: repositories
+/// that were renamed or transferred, archived, disabled, deleted, or are forks.
+///
+/// Failures that aren't known to be permanent (network errors, rate limits) are left out.
+pub fn build_maintenance_report(scan: &ScanResult) -> MaintenanceReport {
+    let mut issues = Vec::new();
+
+    for repository in &scan.repositories {
+        let linked = repository
+            .linked_full_name
+            .clone()
+            .unwrap_or_else(|| repository.full_name.clone());
+        let category = scan.categories.get(&repository.category_key()).cloned();
+        let issue = |kind, action, detail: String| MaintenanceIssue {
+            linked: linked.clone(),
+            category: category.clone(),
+            kind,
+            action,
+            canonical_full_name: Some(repository.full_name.clone()),
+            canonical_url: Some(repository.html_url.clone()),
+            detail,
+        };
+
+        if let Some(linked_full_name) = &repository.linked_full_name {
+            let kind = if same_owner(linked_full_name, &repository.full_name) {
+                MaintenanceIssueKind::Renamed
+            } else {
+                MaintenanceIssueKind::Transferred
+            };
+            issues.push(issue(
+                kind,
+                MaintenanceAction::Update,
+                format!("{} now redirects to {}", linked_full_name, repository.full_name),
+            ));
+        }
+        if repository.disabled {
+            issues.push(issue(
+                MaintenanceIssueKind::Disabled,
+                MaintenanceAction::Remove,
+                String::from("Repository has been disabled by GitHub"),
+            ));
+        } else if repository.archived {
+            issues.push(issue(
+                MaintenanceIssueKind::Archived,
+                MaintenanceAction::Remove,
+                format!("Repository is archived; last updated {}", repository.updated_at),
+            ));
+        }
+        if repository.fork {
+            issues.push(issue(
+                MaintenanceIssueKind::Fork,
+                MaintenanceAction::Review,
+                String::from("Repository is a fork; the upstream project may be the better link"),
+            ));
+        }
+    }
+
+    for failure in scan.failures.iter().filter(|f| f.unavailable) {
+        let linked = format!("{}/{}", failure.owner, failure.repo);
+        issues.push(MaintenanceIssue {
+            category: scan.categories.get(&failure.category_key()).cloned(),
+            linked,
+            kind: MaintenanceIssueKind::Deleted,
+            action: MaintenanceAction::Remove,
+            canonical_full_name: None,
+            canonical_url: None,
+            detail: failure
+                .error
+                .clone()
+                .unwrap_or_else(|| String::from("Repository not found")),
+        });
+    }
+
+    MaintenanceReport {
+        scan_id: scan.scan_id.clone(),
+        list_url: scan.list_url.clone(),
+        scanned_at: scan.scanned_at.clone(),
+        issues,
+    }
+}
+
+fn same_owner(a: &str, b: &str) -> bool {
+    let owner = |name: &str| name.split('/').next().unwrap_or_default().to_lowercase();
+    owner(a) == owner(b)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::github::{Repository, RepositoryFetchResult};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_build_maintenance_report() {
+        let mut categories = HashMap::new();
+        categories.insert(String::from("github.com/new-org/tool"), String::from("Tools"));
+        categories.insert(String::from("github.com/old/gone"), String::from("Libraries"));
+
+        let scan = ScanResult {
+            scan_id: String::from("scan-1"),
+            list_url: String::from("https://github.com/a/awesome"),
+            scanned_at: String::from("2024-03-01T00:00:00+00:00"),
+            cancelled: false,
+            repositories: vec![
+                Repository::base("a/healthy"),
+                Repository {
+                    linked_full_name: Some(String::from("old-org/tool")),
+                    archived: true,
+                    ..Repository::base("new-org/tool")
+                },
+                Repository {
+                    linked_full_name: Some(String::from("a/old-name")),
+                    ..Repository::base("a/new-name")
+                },
+                Repository {
+                    fork: true,
+                    ..Repository::base("b/fork")
+                },
+            ],
+            categories,
+            failures: vec![
+                RepositoryFetchResult {
+                    host: String::from("github.com"),
+                    owner: String::from("old"),
+                    repo: String::from("gone"),
+                    repository: None,
+                    error: Some(String::from("Repository old/gone is unavailable (404 Not Found)")),
+                    unavailable: true,
+                },
+                RepositoryFetchResult {
+                    host: String::from("github.com"),
+                    owner: String::from("c"),
+                    repo: String::from("flaky"),
+                    repository: None,
+                    error: Some(String::from("Failed to fetch repository")),
+                    unavailable: false,
+                },
+            ],
+        };
+
+        let report = build_maintenance_report(&scan);
+        let summary: Vec<(&str, MaintenanceIssueKind, MaintenanceAction)> = report
+            .issues
+            .iter()
+            .map(|i| (i.linked.as_str(), i.kind, i.action))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("old-org/tool", MaintenanceIssueKind::Transferred, MaintenanceAction::Update),
+                ("old-org/tool", MaintenanceIssueKind::Archived, MaintenanceAction::Remove),
+                ("a/old-name", MaintenanceIssueKind::Renamed, MaintenanceAction::Update),
+                ("b/fork", MaintenanceIssueKind::Fork, MaintenanceAction::Review),
+                ("old/gone", MaintenanceIssueKind::Deleted, MaintenanceAction::Remove),
+            ]
+        );
+        assert_eq!(report.issues[0].category.as_deref(), Some("Tools"));
+        assert_eq!(report.issues[0].canonical_url.as_deref(), Some("https://github.com/new-org/tool"));
+        assert_eq!(report.issues[4].category.as_deref(), Some("Libraries"));
+    }
+}
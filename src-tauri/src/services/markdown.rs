@@ -0,0 +1,698 @@
+use crate::models::github::{AwesomeEntry, Forge, ListSection};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::iter::Peekable;
+use std::sync::LazyLock;
+
+/// Sections that only hold navigation and never contain list entries
+const SKIPPED_SECTIONS: &[&str] = &["contents", "table of contents"];
+
+/// First path segments on github.com that are site pages rather than owners
+const RESERVED_OWNERS: &[&str] = &[
+    "sponsors",
+    "topics",
+    "orgs",
+    "marketplace",
+    "features",
+    "collections",
+    "apps",
+    "settings",
+];
+
+/// First path segments on the other forges that are site pages rather than owners
+const RESERVED_FORGE_OWNERS: &[&str] = &["explore", "users", "user", "help", "api", "dashboard", "product", "blog"];
+
+/// Path segments that end a GitLab project path (`/-/tree/...` and older `/tree/...` URLs)
+const GITLAB_PATH_TERMINATORS: &[&str] = &["-", "tree", "blob", "issues", "merge_requests", "wikis", "raw"];
+
+/// Gitea/Forgejo instances recognised without configuration
+const KNOWN_GITEA_HOSTS: &[&str] = &["codeberg.org", "gitea.com"];
+
+/// A bare URL in text
+static URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"https?://[^\s<>()\[\]'`]+").unwrap());
+
+/// The target of an `href` attribute in inline or block HTML
+static HREF_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"href\s*=\s*["']([^"']+)["']"#).unwrap());
+
+/// Owner and repository of a github.com URL
+static GITHUB_REPO_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^https?://(?:www\.)?github\.com/([^/\s?#]+)/([^/\s?#)]+)").unwrap());
+
+/// Host and path of any http(s) URL
+static FORGE_URL_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^https?://(?:www\.)?([^/\s?#]+)(/[^\s?#]*)?").unwrap());
+
+/// A repository link on one of the supported forges
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoLink {
+    pub forge: Forge,
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+#[derive(Default)]
+struct ItemState {
+    entries: Vec<usize>,
+    /// Index into `ParsedList::items` once the item's leading link is seen
+    lead: Option<usize>,
+    first_link_closed: bool,
+    has_nested_list: bool,
+    description: String,
+}
+
+struct LinkState {
+    repo: Option<RepoLink>,
+    text: String,
+    offset: usize,
+}
+
+pub struct ParsedSection {
+    pub level: HeadingLevel,
+    pub title: String,
+    pub anchor: String,
+}
+
+/// An external link anywhere in the README, repository or not
+pub struct ListLink {
+    pub url: String,
+    pub section: Option<usize>,
+    pub offset: usize,
+}
+
+/// A list item that opens with a link, to a repository or any other site
+pub struct ListItem {
+    /// Text of the leading link
+    pub name: String,
+    pub description: Option<String>,
+    pub section: Option<usize>,
+    /// Byte offset of the leading link
+    pub offset: usize,
+}
+
+/// A repository linked again after its first entry, which the entry list leaves out
+pub struct DuplicateLink {
+    /// Index into `entries` of the first occurrence
+    pub first: usize,
+    pub offset: usize,
+    pub section: Option<usize>,
+}
+
+/// Everything one pass over a README finds, with byte offsets into the source
+#[derive(Default)]
+pub struct ParsedList {
+    pub entries: Vec<AwesomeEntry>,
+    pub sections: Vec<ParsedSection>,
+    /// Index into `sections` of the heading each entry was found under
+    pub entry_sections: Vec<Option<usize>>,
+    /// Byte offset of each entry's link
+    pub entry_offsets: Vec<usize>,
+    /// Every list item outside navigation sections that opens with a link, in source order
+    pub items: Vec<ListItem>,
+    pub duplicates: Vec<DuplicateLink>,
+    /// Anchors of every heading, including those outside the section tree
+    pub heading_anchors: HashSet<String>,
+    /// `#fragment` links and their offsets
+    pub anchor_links: Vec<(String, usize)>,
+    /// Every http(s) link and bare URL outside code, including the table of contents
+    pub links: Vec<ListLink>,
+}
+
+/// Parse an awesome list README into structured entries.
+///
+/// Walks the pulldown-cmark event stream so that reference-style links, links
+/// split across lines and links inside HTML blocks are found, while code blocks
+/// and the table of contents are ignored. Each entry records its heading path
+/// (H2 > H3 > H4), the link text and the trailing " - description" of its list item.
+///
+/// Links to GitHub, GitLab, Codeberg, SourceHut and Bitbucket are recognised, plus
+/// any self-hosted Gitea instance named in `gitea_hosts`.
+pub fn parse_awesome_list(markdown: &str, gitea_hosts: &[String]) -> Vec<AwesomeEntry> {
+    parse_list(markdown, gitea_hosts).entries
+}
+
+/// Parse an awesome list README into a tree of sections following its heading structure.
+///
+/// Depth is 1 for top-level sections (normally H2). Entries that appear before the
+/// first section heading are gathered into a leading "Uncategorized" section with
+/// an empty anchor, and the table of contents section is omitted.
+pub fn parse_awesome_list_tree(markdown: &str, gitea_hosts: &[String]) -> Vec<ListSection> {
+    let ParsedList { entries, sections, entry_sections, .. } = parse_list(markdown, gitea_hosts);
+
+    let mut grouped: Vec<Vec<AwesomeEntry>> = vec![Vec::new(); sections.len()];
+    let mut uncategorized = Vec::new();
+    for (entry, section) in entries.into_iter().zip(entry_sections) {
+        match section {
+            Some(index) => grouped[index].push(entry),
+            None => uncategorized.push(entry),
+        }
+    }
+
+    let mut flat = sections
+        .into_iter()
+        .zip(grouped)
+        .filter(|(section, _)| !SKIPPED_SECTIONS.contains(&section.title.to_lowercase().as_str()))
+        .map(|(section, entries)| {
+            let node = ListSection {
+                title: section.title,
+                anchor: section.anchor,
+                depth: 0,
+                sections: Vec::new(),
+                entries,
+            };
+            (section.level, node)
+        })
+        .peekable();
+
+    let mut tree = Vec::new();
+    if !uncategorized.is_empty() {
+        tree.push(ListSection {
+            title: String::from("Uncategorized"),
+            anchor: String::new(),
+            depth: 1,
+            sections: Vec::new(),
+            entries: uncategorized,
+        });
+    }
+    tree.extend(build_tree(&mut flat, 1, None));
+    tree
+}
+
+fn build_tree<I>(sections: &mut Peekable<I>, depth: u8, parent: Option<HeadingLevel>) -> Vec<ListSection>
+where
+    I: Iterator<Item = (HeadingLevel, ListSection)>,
+{
+    let mut nodes = Vec::new();
+    while let Some((level, _)) = sections.peek() {
+        if parent.is_some_and(|p| *level <= p) {
+            break;
+        }
+        let (level, mut node) = sections.next().unwrap();
+        node.depth = depth;
+        node.sections = build_tree(sections, depth + 1, Some(level));
+        nodes.push(node);
+    }
+    nodes
+}
+
+/// Parse an awesome list README in one pass, keeping the source positions the
+/// linter needs alongside the entries and sections
+pub fn parse_list(markdown: &str, gitea_hosts: &[String]) -> ParsedList {
+    let mut parsed = ParsedList::default();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut anchors: HashMap<String, usize> = HashMap::new();
+
+    let mut headings: Vec<(HeadingLevel, String)> = Vec::new();
+    let mut current_section: Option<usize> = None;
+    let mut heading_text: Option<String> = None;
+    let mut skip_section = false;
+    let mut in_code_block = false;
+    let mut items: Vec<ItemState> = Vec::new();
+    let mut link: Option<LinkState> = None;
+
+    let mut push_entry = |parsed: &mut ParsedList,
+                          items: &mut Vec<ItemState>,
+                          headings: &[(HeadingLevel, String)],
+                          section: Option<usize>,
+                          link: RepoLink,
+                          name: String,
+                          offset: usize| {
+        let key = format!("{}/{}/{}", link.host, link.owner, link.repo).to_lowercase();
+        if let Some(&first) = seen.get(&key) {
+            parsed.duplicates.push(DuplicateLink { first, offset, section });
+            return;
+        }
+        seen.insert(key, parsed.entries.len());
+        let name = if name.trim().is_empty() { link.repo.clone() } else { collapse_whitespace(&name) };
+        parsed.entries.push(AwesomeEntry {
+            owner: link.owner,
+            repo: link.repo,
+            forge: link.forge,
+            host: link.host,
+            name,
+            description: None,
+            category_path: headings.iter().map(|(_, title)| title.clone()).collect(),
+        });
+        parsed.entry_sections.push(section);
+        parsed.entry_offsets.push(offset);
+        if let Some(item) = items.last_mut() {
+            item.entries.push(parsed.entries.len() - 1);
+        }
+    };
+
+    // Record the link that opens the innermost list item, if it is the item's first link
+    let lead_item = |parsed: &mut ParsedList, items: &mut Vec<ItemState>, section: Option<usize>, name: String, offset: usize| {
+        if let Some(item) = items.last_mut() {
+            if item.lead.is_none() && !item.first_link_closed {
+                item.lead = Some(parsed.items.len());
+                parsed.items.push(ListItem {
+                    name: collapse_whitespace(&name),
+                    description: None,
+                    section,
+                    offset,
+                });
+            }
+        }
+    };
+
+    for (event, range) in Parser::new_ext(markdown, gfm_options()).into_offset_iter() {
+        let is_text = matches!(event, Event::Text(_));
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading_text = Some(String::new()),
+            Event::End(TagEnd::Heading(level)) => {
+                let title = collapse_whitespace(&heading_text.take().unwrap_or_default());
+                let anchor = unique_anchor(&mut anchors, &title);
+                parsed.heading_anchors.insert(anchor.clone());
+                if level == HeadingLevel::H1 {
+                    headings.clear();
+                    current_section = None;
+                } else if level <= HeadingLevel::H4 {
+                    headings.retain(|(l, _)| *l < level);
+                    headings.push((level, title.clone()));
+                    parsed.sections.push(ParsedSection { level, title, anchor });
+                    current_section = Some(parsed.sections.len() - 1);
+                }
+                skip_section = headings
+                    .iter()
+                    .any(|(_, t)| SKIPPED_SECTIONS.contains(&t.to_lowercase().as_str()));
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::Item) => items.push(ItemState::default()),
+            Event::End(TagEnd::Item) => {
+                if let Some(item) = items.pop() {
+                    let description = clean_description(&item.description);
+                    if let Some(lead) = item.lead {
+                        parsed.items[lead].description = description.clone();
+                    }
+                    for index in item.entries {
+                        if parsed.entries[index].description.is_none() {
+                            parsed.entries[index].description = description.clone();
+                        }
+                    }
+                }
+            }
+            Event::Start(Tag::List(_)) => {
+                if let Some(item) = items.last_mut() {
+                    item.has_nested_list = true;
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) if heading_text.is_none() => {
+                if let Some(fragment) = dest_url.strip_prefix('#') {
+                    parsed.anchor_links.push((fragment.to_string(), range.start));
+                } else if dest_url.starts_with("http://") || dest_url.starts_with("https://") {
+                    parsed.links.push(ListLink {
+                        url: dest_url.to_string(),
+                        section: current_section,
+                        offset: range.start,
+                    });
+                }
+                link = Some(LinkState {
+                    repo: parse_repo_url(&dest_url, gitea_hosts),
+                    text: String::new(),
+                    offset: range.start,
+                });
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some(LinkState { repo, text, offset }) = link.take() {
+                    if !skip_section {
+                        let name = match &repo {
+                            Some(repo) if text.trim().is_empty() => repo.repo.clone(),
+                            _ => text.clone(),
+                        };
+                        lead_item(&mut parsed, &mut items, current_section, name, offset);
+                    }
+                    if let (Some(repo), false) = (repo, skip_section) {
+                        push_entry(&mut parsed, &mut items, &headings, current_section, repo, text.clone(), offset);
+                    }
+                    if let Some(item) = items.last_mut() {
+                        if item.first_link_closed && !item.has_nested_list {
+                            item.description.push_str(&text);
+                        }
+                        item.first_link_closed = true;
+                    }
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = heading_text.as_mut() {
+                    heading.push_str(&text);
+                } else if let Some(link) = link.as_mut() {
+                    link.text.push_str(&text);
+                } else if !in_code_block {
+                    if is_text {
+                        for (start, url) in bare_urls(&text) {
+                            parsed.links.push(ListLink {
+                                url: url.to_string(),
+                                section: current_section,
+                                offset: range.start + start,
+                            });
+                        }
+                    }
+                    if is_text && !skip_section {
+                        for (start, url) in bare_urls(&text) {
+                            if let Some(repo) = parse_repo_url(url, gitea_hosts) {
+                                let offset = range.start + start;
+                                lead_item(&mut parsed, &mut items, current_section, repo.repo.clone(), offset);
+                                push_entry(&mut parsed, &mut items, &headings, current_section, repo, String::new(), offset);
+                            }
+                        }
+                    }
+                    if let Some(item) = items.last_mut() {
+                        if item.first_link_closed && !item.has_nested_list {
+                            item.description.push_str(&text);
+                        }
+                    }
+                }
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                for cap in HREF_PATTERN.captures_iter(&html) {
+                    if cap[1].starts_with("http://") || cap[1].starts_with("https://") {
+                        parsed.links.push(ListLink {
+                            url: cap[1].to_string(),
+                            section: current_section,
+                            offset: range.start + cap.get(0).map_or(0, |m| m.start()),
+                        });
+                    }
+                    if skip_section {
+                        continue;
+                    }
+                    if let Some(repo) = parse_repo_url(&cap[1], gitea_hosts) {
+                        let offset = range.start + cap.get(0).map_or(0, |m| m.start());
+                        lead_item(&mut parsed, &mut items, current_section, repo.repo.clone(), offset);
+                        push_entry(&mut parsed, &mut items, &headings, current_section, repo, String::new(), offset);
+                    }
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(heading) = heading_text.as_mut() {
+                    heading.push(' ');
+                } else if let Some(link) = link.as_mut() {
+                    link.text.push(' ');
+                } else if let Some(item) = items.last_mut() {
+                    if item.first_link_closed && !item.has_nested_list {
+                        item.description.push(' ');
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    parsed
+}
+
+/// Bare URLs in prose with their byte offsets, without the sentence punctuation
+/// that often directly follows them
+fn bare_urls(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    URL_PATTERN
+        .find_iter(text)
+        .map(|url| (url.start(), url.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?'])))
+}
+
+/// The GitHub Flavored Markdown extensions lists use. Smart punctuation stays off so
+/// names, descriptions and headings keep the characters GitHub renders.
+fn gfm_options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS | Options::ENABLE_FOOTNOTES
+}
+
+/// Extract `(owner, repo)` from a github.com URL, ignoring site pages such as sponsors or topics
+pub fn parse_github_repo_url(url: &str) -> Option<(String, String)> {
+    let cap = GITHUB_REPO_PATTERN.captures(url.trim())?;
+
+    let owner = cap.get(1)?.as_str();
+    let repo = cap.get(2)?.as_str().trim_end_matches(".git");
+
+    if repo.is_empty() || repo.contains('&') || RESERVED_OWNERS.contains(&owner.to_lowercase().as_str()) {
+        return None;
+    }
+
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// The flat category of an entry: its innermost heading, or "Uncategorized"
+pub fn entry_category(entry: &AwesomeEntry) -> String {
+    entry
+        .category_path
+        .last()
+        .cloned()
+        .unwrap_or_else(|| String::from("Uncategorized"))
+}
+
+/// Recognise a repository URL on any supported forge.
+///
+/// GitLab project paths keep their subgroups in `owner` (`group/subgroup`), and
+/// SourceHut owners keep their leading `~`.
+pub fn parse_repo_url(url: &str, gitea_hosts: &[String]) -> Option<RepoLink> {
+    let cap = FORGE_URL_PATTERN.captures(url.trim())?;
+
+    let host = cap.get(1)?.as_str().to_lowercase();
+    let segments: Vec<&str> = cap
+        .get(2)
+        .map(|m| m.as_str())
+        .unwrap_or("")
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let forge = match host.as_str() {
+        "github.com" => {
+            let (owner, repo) = parse_github_repo_url(url)?;
+            return Some(RepoLink { forge: Forge::GitHub, host, owner, repo });
+        }
+        "gitlab.com" => Forge::GitLab,
+        "git.sr.ht" => Forge::SourceHut,
+        "bitbucket.org" => Forge::Bitbucket,
+        h if KNOWN_GITEA_HOSTS.contains(&h) || gitea_hosts.iter().any(|g| g.eq_ignore_ascii_case(h)) => Forge::Gitea,
+        _ => return None,
+    };
+
+    let (owner, repo) = match forge {
+        Forge::GitLab => {
+            let end = segments
+                .iter()
+                .position(|s| GITLAB_PATH_TERMINATORS.contains(s))
+                .unwrap_or(segments.len());
+            let path = &segments[..end];
+            if path.len() < 2 {
+                return None;
+            }
+            (path[..path.len() - 1].join("/"), path[path.len() - 1].to_string())
+        }
+        Forge::SourceHut if !segments.first()?.starts_with('~') => return None,
+        _ => (segments.first()?.to_string(), segments.get(1)?.to_string()),
+    };
+    let repo = repo.trim_end_matches(".git").to_string();
+
+    if repo.is_empty() || RESERVED_FORGE_OWNERS.contains(&owner.to_lowercase().as_str()) {
+        return None;
+    }
+
+    Some(RepoLink { forge, host, owner, repo })
+}
+
+/// Build the anchor GitHub generates for a heading: lowercase, punctuation
+/// removed, spaces turned into hyphens
+pub fn github_anchor(title: &str) -> String {
+    title
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+/// GitHub suffixes repeated anchors with `-1`, `-2`, ... in document order
+pub fn unique_anchor(anchors: &mut HashMap<String, usize>, title: &str) -> String {
+    let anchor = github_anchor(title);
+    let count = anchors.entry(anchor.clone()).or_insert(0);
+    let unique = if *count == 0 { anchor } else { format!("{}-{}", anchor, count) };
+    *count += 1;
+    unique
+}
+
+/// Byte offsets of line starts, for turning offsets into line and column
+pub struct LineIndex {
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        Self { starts }
+    }
+
+    /// 1-based line and column, counting columns in characters
+    pub fn position(&self, text: &str, offset: usize) -> (u32, u32) {
+        let line = self.starts.partition_point(|&start| start <= offset) - 1;
+        let column = text[self.starts[line]..offset].chars().count() + 1;
+        (line as u32 + 1, column as u32)
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Strip the separator awesome lists put between the link and its description
+fn clean_description(text: &str) -> Option<String> {
+    let text = collapse_whitespace(text);
+    let text = text
+        .trim_start_matches(|c: char| c == '-' || c == '–' || c == '—' || c == ':' || c.is_whitespace())
+        .trim();
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_awesome_list() {
+        let markdown = r#"
+# Awesome Things
+
+## Contents
+
+- [Databases](#databases)
+- [Skipped](https://github.com/toc/link)
+
+## Databases
+
+- [Alpha](https://github.com/org/alpha) - Fast key-value store.
+- [Beta][beta] - Reference-style link.
+
+### Embedded
+
+#### Pure Rust
+
+- [Gamma](https://github.com/org/gamma
+  ) - Split
+  across lines.
+
+<p><a href="https://github.com/org/delta">Delta</a></p>
+
+```
+- [Ignored](https://github.com/org/ignored)
+```
+
+[beta]: https://github.com/org/beta
+        "#;
+
+        let entries = parse_awesome_list(markdown, &[]);
+        let names: Vec<_> = entries.iter().map(|e| e.repo.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "beta", "gamma", "delta"]);
+
+        assert_eq!(entries[0].name, "Alpha");
+        assert_eq!(entries[0].description.as_deref(), Some("Fast key-value store."));
+        assert_eq!(entries[0].category_path, vec!["Databases"]);
+        assert_eq!(entries[1].description.as_deref(), Some("Reference-style link."));
+        assert_eq!(entries[2].category_path, vec!["Databases", "Embedded", "Pure Rust"]);
+        assert_eq!(entries[2].description.as_deref(), Some("Split across lines."));
+    }
+
+    #[test]
+    fn test_parse_awesome_list_tree() {
+        let markdown = r#"
+# Awesome Things
+
+- [Intro](https://github.com/org/intro)
+
+## Databases
+
+- [Alpha](https://github.com/org/alpha)
+
+### Embedded
+
+- [Beta](https://github.com/org/beta)
+
+## Web
+
+### Embedded
+
+- [Gamma](https://github.com/org/gamma)
+        "#;
+
+        let tree = parse_awesome_list_tree(markdown, &[]);
+        let titles: Vec<_> = tree.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Uncategorized", "Databases", "Web"]);
+
+        let databases = &tree[1];
+        assert_eq!(databases.anchor, "databases");
+        assert_eq!(databases.entries[0].repo, "alpha");
+        assert_eq!(databases.sections[0].depth, 2);
+        assert_eq!(databases.sections[0].anchor, "embedded");
+        assert_eq!(databases.sections[0].entries[0].repo, "beta");
+
+        let web_embedded = &tree[2].sections[0];
+        assert_eq!(web_embedded.anchor, "embedded-1");
+        assert_eq!(web_embedded.entries[0].repo, "gamma");
+    }
+
+    #[test]
+    fn test_bare_urls_drop_trailing_punctuation() {
+        let markdown = "See https://example.com/page. Also https://x.y/a, https://x.y/b?q=1! and https://github.com/org/tool.\n";
+
+        let parsed = parse_list(markdown, &[]);
+        let urls: Vec<&str> = parsed.links.iter().map(|l| l.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://example.com/page", "https://x.y/a", "https://x.y/b?q=1", "https://github.com/org/tool"]
+        );
+        assert_eq!(parsed.links[0].offset, 4);
+        assert_eq!(parsed.entries[0].repo, "tool");
+    }
+
+    #[test]
+    fn test_parse_list_keeps_punctuation() {
+        let markdown = r#"
+## Tools -- CLI
+
+- [Don't "Panic"](https://github.com/org/dont-panic) - It's fine... really.
+"#;
+
+        let parsed = parse_list(markdown, &[]);
+        assert_eq!(parsed.sections[0].title, "Tools -- CLI");
+        assert!(parsed.heading_anchors.contains("tools----cli"));
+        assert_eq!(parsed.entries[0].name, "Don't \"Panic\"");
+        assert_eq!(parsed.entries[0].description.as_deref(), Some("It's fine... really."));
+    }
+
+    #[test]
+    fn test_parse_repo_url_other_forges() {
+        let gitea_hosts = vec!["git.example.com".to_string()];
+        let parse = |url: &str| {
+            parse_repo_url(url, &gitea_hosts).map(|l| (l.forge, l.host, l.owner, l.repo))
+        };
+
+        assert_eq!(
+            parse("https://gitlab.com/group/subgroup/project/-/tree/main"),
+            Some((Forge::GitLab, "gitlab.com".into(), "group/subgroup".into(), "project".into()))
+        );
+        assert_eq!(
+            parse("https://codeberg.org/forgejo/forgejo"),
+            Some((Forge::Gitea, "codeberg.org".into(), "forgejo".into(), "forgejo".into()))
+        );
+        assert_eq!(
+            parse("https://git.sr.ht/~sircmpwn/aerc"),
+            Some((Forge::SourceHut, "git.sr.ht".into(), "~sircmpwn".into(), "aerc".into()))
+        );
+        assert_eq!(
+            parse("https://bitbucket.org/atlassian/python-bitbucket.git"),
+            Some((Forge::Bitbucket, "bitbucket.org".into(), "atlassian".into(), "python-bitbucket".into()))
+        );
+        assert_eq!(
+            parse("https://git.example.com/team/tool"),
+            Some((Forge::Gitea, "git.example.com".into(), "team".into(), "tool".into()))
+        );
+        assert_eq!(parse("https://gitlab.com/explore/projects"), None);
+        assert_eq!(parse("https://example.org/team/tool"), None);
+    }
+}
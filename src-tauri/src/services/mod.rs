@@ -1,2 +1,19 @@
+pub mod added_dates;
+pub mod checkpoint;
+pub mod crawler;
+pub mod export;
+pub mod forges;
 pub mod github;
 pub mod gitlab;
+pub mod health;
+pub mod history;
+pub mod http_cache;
+pub mod import;
+pub mod links;
+pub mod lint;
+pub mod local;
+pub mod maintenance;
+pub mod markdown;
+pub mod rate_limit;
+pub mod scanner;
+pub mod trends;
@@ -0,0 +1,143 @@
+use crate::models::github::RateLimitInfo;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How many times a rate-limited request is retried before giving up
+pub const MAX_RATE_LIMIT_RETRIES: u32 = 4;
+
+/// GitHub asks clients to wait at least a minute after a secondary limit without `Retry-After`
+const SECONDARY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Longest we are willing to sleep for a primary limit to reset
+const MAX_RESET_WAIT: Duration = Duration::from_secs(60 * 60);
+
+/// Length of GitHub's primary rate limit window
+const RATE_LIMIT_WINDOW_SECS: u64 = 60 * 60;
+
+/// Quota last reported by GitHub for each rate limit resource (`core`, `graphql`, `search`, ...),
+/// shared by every client created with the same token
+#[derive(Default)]
+pub struct RateLimitTracker {
+    resources: Mutex<HashMap<String, RateLimitInfo>>,
+}
+
+impl RateLimitTracker {
+    /// Record the quota from a response's `X-RateLimit-*` headers
+    pub fn update_from_headers(&self, headers: &HeaderMap) {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let number = |name: &str| header(name).and_then(|v| v.parse::<u64>().ok());
+
+        let (Some(limit), Some(remaining), Some(reset)) = (
+            number("x-ratelimit-limit"),
+            number("x-ratelimit-remaining"),
+            number("x-ratelimit-reset"),
+        ) else {
+            return;
+        };
+
+        self.update(RateLimitInfo {
+            resource: header("x-ratelimit-resource").unwrap_or("core").to_string(),
+            limit: limit as u32,
+            remaining: remaining as u32,
+            used: number("x-ratelimit-used").unwrap_or(limit.saturating_sub(remaining)) as u32,
+            reset,
+        });
+    }
+
+    pub fn update(&self, info: RateLimitInfo) {
+        self.resources.lock().unwrap().insert(info.resource.clone(), info);
+    }
+
+    /// Forget all recorded quota, e.g. when the token changes
+    pub fn clear(&self) {
+        self.resources.lock().unwrap().clear();
+    }
+
+    pub fn snapshot(&self) -> Vec<RateLimitInfo> {
+        let mut resources: Vec<_> = self.resources.lock().unwrap().values().cloned().collect();
+        resources.sort_by(|a, b| a.resource.cmp(&b.resource));
+        resources
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Decide how long to wait before retrying a 403/429 response, if it is a rate limit at all.
+///
+/// `Retry-After` (secondary limits) wins; an exhausted primary quota waits until
+/// `X-RateLimit-Reset`; otherwise a secondary limit backs off exponentially.
+/// `mentions_rate_limit` says whether the response body blamed a rate limit.
+pub fn retry_delay(status: StatusCode, headers: &HeaderMap, attempt: u32, mentions_rate_limit: bool) -> Option<Duration> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(seconds) = header("retry-after").and_then(|v| v.parse::<u64>().ok()) {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    if header("x-ratelimit-remaining") == Some("0") {
+        let reset = header("x-ratelimit-reset").and_then(|v| v.parse::<u64>().ok())?;
+        let wait = Duration::from_secs(reset.saturating_sub(unix_now()) + 1);
+        return (wait <= MAX_RESET_WAIT).then_some(wait);
+    }
+
+    if status == StatusCode::TOO_MANY_REQUESTS || mentions_rate_limit {
+        return Some(SECONDARY_BACKOFF * 2u32.pow(attempt));
+    }
+
+    None
+}
+
+/// Seconds a scan needing `requests` calls will spend waiting for quota to reset,
+/// assuming it starts now and nothing else spends the quota
+pub fn quota_wait_seconds(info: &RateLimitInfo, requests: u32, now: u64) -> u64 {
+    if requests <= info.remaining || info.limit == 0 {
+        return 0;
+    }
+
+    let shortfall = (requests - info.remaining) as u64;
+    let extra_windows = (shortfall - 1) / info.limit as u64;
+    info.reset.saturating_sub(now) + extra_windows * RATE_LIMIT_WINDOW_SECS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_wait_seconds() {
+        let info = RateLimitInfo {
+            resource: "core".to_string(),
+            limit: 5000,
+            remaining: 100,
+            used: 4900,
+            reset: 1_000 + 600,
+        };
+
+        assert_eq!(quota_wait_seconds(&info, 50, 1_000), 0);
+        assert_eq!(quota_wait_seconds(&info, 700, 1_000), 600);
+        assert_eq!(quota_wait_seconds(&info, 100 + 5000 + 1, 1_000), 600 + 3600);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_delay(StatusCode::NOT_FOUND, &headers, 0, false), None);
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &headers, 0, false), None);
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &headers, 1, true), Some(Duration::from_secs(120)));
+
+        headers.insert("retry-after", "30".parse().unwrap());
+        assert_eq!(retry_delay(StatusCode::TOO_MANY_REQUESTS, &headers, 0, false), Some(Duration::from_secs(30)));
+    }
+}
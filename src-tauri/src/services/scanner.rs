@@ -0,0 +1,312 @@
+use crate::models::github::{AwesomeEntry, Forge, Repository, RepositoryFetchResult, ScanResult};
+use crate::services::forges::ForgeClient;
+use crate::services::github::{GitHubClient, GRAPHQL_BATCH_SIZE};
+use crate::services::markdown::entry_category;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Repository fetches running at once when the caller doesn't choose
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Optional data fetched for each GitHub repository after its metadata, each costing
+/// extra API requests per repository
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrichOptions {
+    pub releases: bool,
+    /// Contributor, commit activity and language statistics, at three to seven requests
+    pub activity: bool,
+}
+
+impl EnrichOptions {
+    /// Whether any optional data was requested
+    pub fn any(&self) -> bool {
+        self.releases || self.activity
+    }
+}
+
+/// Key identifying an entry across scans of the same list
+pub fn entry_key(entry: &AwesomeEntry) -> String {
+    format!("{}/{}/{}", entry.host, entry.owner, entry.repo).to_lowercase()
+}
+
+/// Entries a scan task fetched: one on its own, or a whole GraphQL batch
+struct Fetched {
+    results: Vec<(AwesomeEntry, RepositoryFetchResult)>,
+    /// Whether the requested enrichment has already been done
+    enriched: bool,
+}
+
+/// Fetch metadata for the given entries with at most `concurrency` requests in flight.
+///
+/// With a token, GitHub entries are looked up in GraphQL batches of up to 100 and
+/// only their optional enrichment costs REST requests; without one, and for other
+/// forges, each entry is fetched on its own.
+///
+/// `on_result` is called as each repository completes, in completion order. Once
+/// `cancelled` is set no new fetches start and waits on GitHub are abandoned.
+/// Entries that never ran, failed after cancellation or were still being enriched
+/// are left out of the returned results, which are keyed by `entry_key`.
+pub async fn scan_entries<F>(
+    client: GitHubClient,
+    entries: Vec<AwesomeEntry>,
+    concurrency: usize,
+    enrich: EnrichOptions,
+    cancelled: Arc<AtomicBool>,
+    mut on_result: F,
+) -> HashMap<String, RepositoryFetchResult>
+where
+    F: FnMut(&AwesomeEntry, &RepositoryFetchResult),
+{
+    let client = Arc::new(client);
+    let forge_client = Arc::new(ForgeClient::new());
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+    let (batched, single): (Vec<AwesomeEntry>, Vec<AwesomeEntry>) = entries
+        .into_iter()
+        .partition(|entry| entry.forge == Forge::GitHub && client.is_authenticated());
+
+    let mut tasks = JoinSet::new();
+    for chunk in batched.chunks(GRAPHQL_BATCH_SIZE) {
+        let chunk = chunk.to_vec();
+        let client = Arc::clone(&client);
+        let semaphore = Arc::clone(&semaphore);
+        let cancelled = Arc::clone(&cancelled);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            if cancelled.load(Ordering::SeqCst) {
+                return None;
+            }
+            let repos: Vec<(String, String)> = chunk.iter().map(|e| (e.owner.clone(), e.repo.clone())).collect();
+            let results = client.fetch_repositories_bulk(&repos).await;
+            Some(Fetched {
+                results: chunk.into_iter().zip(results).collect(),
+                enriched: false,
+            })
+        });
+    }
+    for entry in single {
+        let client = Arc::clone(&client);
+        let forge_client = Arc::clone(&forge_client);
+        let semaphore = Arc::clone(&semaphore);
+        let cancelled = Arc::clone(&cancelled);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            if cancelled.load(Ordering::SeqCst) {
+                return None;
+            }
+            let result = fetch_entry(&client, &forge_client, &entry, enrich).await;
+            // Cancellation cuts enrichment short without failing it, so the entry
+            // can't be trusted to be complete and is left for a resumed scan
+            if enrich.any() && cancelled.load(Ordering::SeqCst) {
+                return None;
+            }
+            let result = RepositoryFetchResult::from_result(&entry.host, &entry.owner, &entry.repo, result);
+            Some(Fetched {
+                results: vec![(entry, result)],
+                enriched: true,
+            })
+        });
+    }
+
+    let mut results = HashMap::new();
+    while let Some(joined) = tasks.join_next().await {
+        let Ok(Some(fetched)) = joined else {
+            continue;
+        };
+        for (entry, mut result) in fetched.results {
+            // A fetch that failed after cancellation was most likely cut short by it,
+            // so the entry is left for a resumed scan rather than reported as failed
+            if result.repository.is_none() && cancelled.load(Ordering::SeqCst) {
+                continue;
+            }
+            // Enrichment is per repository, so a batch fans back out into one task each
+            let pending = result.repository.take_if(|_| !fetched.enriched && enrich.any());
+            if let Some(mut repository) = pending {
+                let client = Arc::clone(&client);
+                let semaphore = Arc::clone(&semaphore);
+                let cancelled = Arc::clone(&cancelled);
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    if cancelled.load(Ordering::SeqCst) {
+                        return None;
+                    }
+                    enrich_repository(&client, &mut repository, enrich).await;
+                    if cancelled.load(Ordering::SeqCst) {
+                        return None;
+                    }
+                    result.repository = Some(repository);
+                    Some(Fetched {
+                        results: vec![(entry, result)],
+                        enriched: true,
+                    })
+                });
+            } else {
+                on_result(&entry, &result);
+                results.insert(entry_key(&entry), result);
+            }
+        }
+    }
+
+    results
+}
+
+/// Assemble the final dataset in list order from the results gathered so far
+pub fn build_scan_result(
+    scan_id: &str,
+    list_url: &str,
+    entries: &[AwesomeEntry],
+    mut results: HashMap<String, RepositoryFetchResult>,
+    cancelled: bool,
+) -> ScanResult {
+    let mut repositories = Vec::new();
+    let mut categories = HashMap::new();
+    let mut failures = Vec::new();
+    for entry in entries {
+        match results.remove(&entry_key(entry)) {
+            Some(RepositoryFetchResult { repository: Some(repository), .. }) => {
+                categories.insert(repository.category_key(), entry_category(entry));
+                repositories.push(repository);
+            }
+            Some(failure) => {
+                categories.insert(failure.category_key(), entry_category(entry));
+                failures.push(failure);
+            }
+            None => {}
+        }
+    }
+
+    ScanResult {
+        scan_id: scan_id.to_string(),
+        list_url: list_url.to_string(),
+        scanned_at: chrono::Utc::now().to_rfc3339(),
+        cancelled,
+        repositories,
+        categories,
+        failures,
+    }
+}
+
+async fn fetch_entry(
+    client: &GitHubClient,
+    forge_client: &ForgeClient,
+    entry: &AwesomeEntry,
+    enrich: EnrichOptions,
+) -> Result<Repository> {
+    match entry.forge {
+        Forge::GitHub => {
+            let mut repository = client.fetch_repository(&entry.owner, &entry.repo).await?;
+            enrich_repository(client, &mut repository, enrich).await;
+            Ok(repository)
+        }
+        forge => {
+            forge_client
+                .fetch_repository(forge, &entry.host, &entry.owner, &entry.repo)
+                .await
+        }
+    }
+}
+
+/// Add the requested optional data to a GitHub repository. Enrichment that fails
+/// leaves its fields unset rather than failing an otherwise complete repository.
+pub async fn enrich_repository(client: &GitHubClient, repository: &mut Repository, enrich: EnrichOptions) {
+    let Some((owner, repo)) = repository.full_name.split_once('/') else {
+        return;
+    };
+    let (owner, repo) = (owner.to_string(), repo.to_string());
+
+    if enrich.releases {
+        if let Ok((latest, count)) = client.fetch_releases(&owner, &repo).await {
+            repository.latest_release = latest;
+            repository.release_count = Some(count);
+        }
+    }
+    if enrich.activity {
+        if let Ok(activity) = client.fetch_activity(&owner, &repo).await {
+            repository.activity = Some(activity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(host: &str, owner: &str, repo: &str, category_path: &[&str]) -> AwesomeEntry {
+        AwesomeEntry {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            forge: if host == "github.com" { Forge::GitHub } else { Forge::GitLab },
+            host: host.to_string(),
+            name: repo.to_string(),
+            description: None,
+            category_path: category_path.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_entry_key() {
+        assert_eq!(entry_key(&entry("github.com", "Org", "Tool", &[])), "github.com/org/tool");
+        assert_ne!(
+            entry_key(&entry("github.com", "org", "tool", &[])),
+            entry_key(&entry("gitlab.com", "org", "tool", &[]))
+        );
+    }
+
+    #[test]
+    fn test_build_scan_result() {
+        let entries = vec![
+            entry("github.com", "org", "first", &["Tools", "CLI"]),
+            entry("github.com", "old", "gone", &["Libraries"]),
+            entry("github.com", "org", "renamed", &[]),
+            entry("github.com", "org", "pending", &["Tools"]),
+            entry("gitlab.com", "org", "first", &["Mirrors"]),
+        ];
+        let mut results = HashMap::new();
+        results.insert(
+            entry_key(&entries[2]),
+            RepositoryFetchResult::from_result("github.com", "org", "renamed", Ok(Repository::base("org/new-name"))),
+        );
+        results.insert(
+            entry_key(&entries[0]),
+            RepositoryFetchResult::from_result("github.com", "org", "first", Ok(Repository::base("org/first"))),
+        );
+        results.insert(
+            entry_key(&entries[1]),
+            RepositoryFetchResult::from_result("github.com", "old", "gone", Err(anyhow::anyhow!("Repository not found"))),
+        );
+        results.insert(
+            entry_key(&entries[4]),
+            RepositoryFetchResult::from_result(
+                "gitlab.com",
+                "org",
+                "first",
+                Ok(Repository {
+                    html_url: String::from("https://gitlab.com/org/first"),
+                    ..Repository::base("org/first")
+                }),
+            ),
+        );
+
+        let result = build_scan_result("scan-1", "https://github.com/org/awesome", &entries, results, true);
+        assert!(result.cancelled);
+        let names: Vec<&str> = result.repositories.iter().map(|r| r.full_name.as_str()).collect();
+        assert_eq!(names, vec!["org/first", "org/new-name", "org/first"]);
+        assert_eq!(result.repositories[1].linked_full_name.as_deref(), Some("org/renamed"));
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].error.as_deref(), Some("Repository not found"));
+
+        // Categories are keyed by host and canonical name, failures by their link, so the
+        // same name on two forges keeps both categories
+        assert_eq!(result.categories["github.com/org/first"], "CLI");
+        assert_eq!(result.categories["gitlab.com/org/first"], "Mirrors");
+        assert_eq!(result.categories["github.com/org/new-name"], "Uncategorized");
+        assert_eq!(result.categories["github.com/old/gone"], "Libraries");
+        // Entries that never ran are left out
+        assert!(!result.categories.contains_key("github.com/org/pending"));
+    }
+}
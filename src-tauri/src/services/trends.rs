@@ -0,0 +1,153 @@
+use crate::models::github::{MetricPoint, RepositoryDelta, RepositoryTrend, ScanComparison};
+use std::collections::HashMap;
+
+/// Compute per-repository deltas between the metric snapshots of two scans.
+///
+/// Repositories are matched case-insensitively by `full_name`. Deltas are ordered
+/// by stars gained, so the fastest-rising projects come first.
+pub fn compare_metrics(
+    from_scan_id: &str,
+    from: &[(String, MetricPoint)],
+    to_scan_id: &str,
+    to: &[(String, MetricPoint)],
+) -> ScanComparison {
+    let earlier: HashMap<String, &MetricPoint> = from
+        .iter()
+        .map(|(full_name, point)| (full_name.to_lowercase(), point))
+        .collect();
+    let later_names: Vec<String> = to.iter().map(|(full_name, _)| full_name.to_lowercase()).collect();
+
+    let mut deltas: Vec<RepositoryDelta> = to
+        .iter()
+        .map(|(full_name, point)| {
+            let before = earlier.get(&full_name.to_lowercase());
+            let gained = |now: u32, then: fn(&MetricPoint) -> u32| {
+                now as i64 - before.map(|b| then(b) as i64).unwrap_or(0)
+            };
+            RepositoryDelta {
+                full_name: full_name.clone(),
+                stargazers_count: point.stargazers_count,
+                stars_gained: gained(point.stargazers_count, |p| p.stargazers_count),
+                forks_gained: gained(point.forks_count, |p| p.forks_count),
+                issues_opened: gained(point.open_issues_count, |p| p.open_issues_count),
+                newly_archived: point.archived && before.is_some_and(|b| !b.archived),
+                is_new: before.is_none(),
+            }
+        })
+        .collect();
+    deltas.sort_by(|a, b| {
+        b.stars_gained
+            .cmp(&a.stars_gained)
+            .then_with(|| b.stargazers_count.cmp(&a.stargazers_count))
+    });
+
+    let removed = from
+        .iter()
+        .filter(|(full_name, _)| !later_names.contains(&full_name.to_lowercase()))
+        .map(|(full_name, _)| full_name.clone())
+        .collect();
+
+    ScanComparison {
+        from_scan_id: from_scan_id.to_string(),
+        to_scan_id: to_scan_id.to_string(),
+        from_scanned_at: from.first().map(|(_, p)| p.scanned_at.clone()).unwrap_or_default(),
+        to_scanned_at: to.first().map(|(_, p)| p.scanned_at.clone()).unwrap_or_default(),
+        deltas,
+        removed,
+    }
+}
+
+/// Group metric snapshots (oldest first) into one time series per repository,
+/// ordered by stars gained between each repository's first and last scan
+pub fn build_trends(metrics: Vec<(String, MetricPoint)>) -> Vec<RepositoryTrend> {
+    let mut trends: Vec<RepositoryTrend> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (full_name, point) in metrics {
+        let key = full_name.to_lowercase();
+        match index.get(&key) {
+            Some(&i) => {
+                // Keep the most recent spelling of the name
+                trends[i].full_name = full_name;
+                trends[i].points.push(point);
+            }
+            None => {
+                index.insert(key, trends.len());
+                trends.push(RepositoryTrend {
+                    full_name,
+                    points: vec![point],
+                });
+            }
+        }
+    }
+
+    let growth = |trend: &RepositoryTrend| match (trend.points.first(), trend.points.last()) {
+        (Some(first), Some(last)) => last.stargazers_count as i64 - first.stargazers_count as i64,
+        _ => 0,
+    };
+    trends.sort_by_key(|trend| std::cmp::Reverse(growth(trend)));
+    trends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(scan_id: &str, stars: u32, forks: u32, issues: u32, archived: bool) -> MetricPoint {
+        MetricPoint {
+            scan_id: scan_id.to_string(),
+            scanned_at: format!("{}-at", scan_id),
+            stargazers_count: stars,
+            forks_count: forks,
+            open_issues_count: issues,
+            archived,
+        }
+    }
+
+    #[test]
+    fn test_compare_metrics() {
+        let from = vec![
+            (String::from("a/big"), point("s1", 1000, 100, 10, false)),
+            (String::from("a/rising"), point("s1", 50, 5, 2, false)),
+            (String::from("a/dropped"), point("s1", 10, 1, 0, false)),
+        ];
+        let to = vec![
+            (String::from("a/big"), point("s2", 1010, 101, 8, true)),
+            (String::from("A/Rising"), point("s2", 250, 20, 5, false)),
+            (String::from("a/new"), point("s2", 30, 3, 1, false)),
+        ];
+
+        let comparison = compare_metrics("s1", &from, "s2", &to);
+        assert_eq!(comparison.from_scanned_at, "s1-at");
+        assert_eq!(comparison.removed, vec![String::from("a/dropped")]);
+
+        let names: Vec<&str> = comparison.deltas.iter().map(|d| d.full_name.as_str()).collect();
+        assert_eq!(names, vec!["A/Rising", "a/new", "a/big"]);
+
+        let rising = &comparison.deltas[0];
+        assert_eq!((rising.stars_gained, rising.forks_gained, rising.issues_opened), (200, 15, 3));
+        assert!(!rising.is_new);
+
+        assert!(comparison.deltas[1].is_new);
+        assert_eq!(comparison.deltas[1].stars_gained, 30);
+
+        let big = &comparison.deltas[2];
+        assert_eq!(big.issues_opened, -2);
+        assert!(big.newly_archived);
+    }
+
+    #[test]
+    fn test_build_trends() {
+        let trends = build_trends(vec![
+            (String::from("a/steady"), point("s1", 100, 0, 0, false)),
+            (String::from("a/rising"), point("s1", 10, 0, 0, false)),
+            (String::from("a/steady"), point("s2", 101, 0, 0, false)),
+            (String::from("A/Rising"), point("s2", 90, 0, 0, false)),
+        ]);
+
+        assert_eq!(trends.len(), 2);
+        assert_eq!(trends[0].full_name, "A/Rising");
+        assert_eq!(trends[0].points.len(), 2);
+        assert_eq!(trends[0].points[1].stargazers_count, 90);
+        assert_eq!(trends[1].full_name, "a/steady");
+    }
+}
@@ -1,13 +1,27 @@
-import { useState } from 'react';
+import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { save } from '@tauri-apps/plugin-dialog';
+import { listen } from '@tauri-apps/api/event';
+import { open, save } from '@tauri-apps/plugin-dialog';
 import { sendNotification } from '@tauri-apps/plugin-notification';
-import type { Repository, SavedList } from '../../types';
+import type {
+  BackendScanResult,
+  HealthScoreResult,
+  ImportedRepositories,
+  Repository,
+  RepositoryFetchResult,
+  SavedList,
+  ScanCheckpointSummary,
+  ScanProgress,
+  ScanStarted
+} from '../../types';
 import { savedListsStorage } from '../../utils/storage';
 import InputForm from './InputForm';
-import RepositoryTable from './RepositoryTable';
+import RepositoryTable, { type ExportFormat } from './RepositoryTable';
 import SavedLists from './SavedLists';
 
+// Scan results key categories by host and path, e.g. "github.com/owner/name"
+const categoryKey = (repo: Repository) => repo.html_url.replace(/^https?:\/\//, '').replace(/\/$/, '');
+
 export default function GitHubScanner() {
   const [repositories, setRepositories] = useState<Repository[]>([]);
   const [isScanning, setIsScanning] = useState(false);
@@ -18,83 +32,147 @@ export default function GitHubScanner() {
   const [currentListUrl, setCurrentListUrl] = useState('');
   const [currentListName, setCurrentListName] = useState('');
   const [showSaveDialog, setShowSaveDialog] = useState(false);
+  const [includeReleases, setIncludeReleases] = useState(false);
+  const [includeActivity, setIncludeActivity] = useState(false);
+  const [isScoring, setIsScoring] = useState(false);
+  const [scannedAt, setScannedAt] = useState<string | null>(null);
+  const [activeScanId, setActiveScanId] = useState<string | null>(null);
+  const [resumePrompt, setResumePrompt] = useState<ScanCheckpointSummary | null>(null);
+
+  const showScanResult = (result: BackendScanResult) => {
+    setRepositories(result.repositories.map(repo => ({
+      ...repo,
+      category: result.categories[categoryKey(repo)]
+    })));
+    setCurrentListUrl(result.list_url);
+    setScannedAt(result.scanned_at);
+  };
 
-  const handleScan = async (url: string) => {
-    setIsScanning(true);
-    setError('');
-    setRepositories([]);
-    setProgress({ current: 0, total: 0 });
-    setCurrentListUrl(url);
+  // Show the previous scan of this session again when the page is reopened
+  useEffect(() => {
+    invoke<BackendScanResult | null>('get_last_scan_result')
+      .then(result => {
+        if (result) {
+          showScanResult(result);
+        }
+      })
+      .catch(err => console.error('Failed to load the last scan:', err));
+  }, []);
 
+  // Offer to resume a list whose previous scan was cancelled part way
+  const handleScan = async (url: string) => {
     try {
-      // Fetch the list of repository links from the awesome list with categories
-      const repoLinks = await invoke<[string, string, string][]>('fetch_awesome_list_with_categories', { url });
-
-      if (repoLinks.length === 0) {
-        setError('No repositories found in this awesome list');
-        setIsScanning(false);
+      const checkpoint = await invoke<ScanCheckpointSummary | null>('get_scan_checkpoint', {
+        url,
+        includeReleases,
+        includeActivity
+      });
+      if (checkpoint) {
+        setResumePrompt(checkpoint);
         return;
       }
+    } catch (err) {
+      console.error('Failed to check for an interrupted scan:', err);
+    }
+    await runScan(url, false);
+  };
 
-      setProgress({ current: 0, total: repoLinks.length });
-
-      // Fetch information for each repository
-      const repos: Repository[] = [];
-      let rateLimitHit = false;
+  const handleCancelScan = async () => {
+    if (!activeScanId) {
+      return;
+    }
+    try {
+      await invoke('cancel_scan', { scanId: activeScanId });
+    } catch (err) {
+      console.error('Failed to cancel scan:', err);
+    }
+  };
 
-      for (let i = 0; i < repoLinks.length; i++) {
-        const [owner, repo, category] = repoLinks[i];
+  const runScan = async (url: string, resume: boolean) => {
+    setResumePrompt(null);
+    setIsScanning(true);
+    setError('');
+    setRepositories([]);
+    setProgress({ current: 0, total: 0 });
+    setCurrentListUrl(url);
 
-        try {
-          const repoInfo = await invoke<Repository>('fetch_repo_info', { owner, repo });
-          repos.push({ ...repoInfo, category });
-          setProgress({ current: i + 1, total: repoLinks.length });
+    // The backend fetches every entry and streams each repository as it arrives
+    const scanId = `scan-${Date.now()}`;
+    setActiveScanId(scanId);
+    const repos: Repository[] = [];
+    const unlisteners = await Promise.all([
+      listen<ScanStarted>('scan-started', event => {
+        if (event.payload.scan_id === scanId) {
+          setProgress({ current: event.payload.resumed, total: event.payload.total });
+        }
+      }),
+      listen<ScanProgress>('scan-progress', event => {
+        if (event.payload.scanId === scanId) {
+          setProgress({ current: event.payload.current, total: event.payload.total });
+        }
+      }),
+      listen<RepositoryFetchResult>('scan-repository', event => {
+        if (event.payload.repository) {
+          repos.push(event.payload.repository);
           setRepositories([...repos]);
-        } catch (err) {
-          console.error(`Failed to fetch ${owner}/${repo}:`, err);
-
-          // Check if it's a rate limit error
-          const errorMsg = err instanceof Error ? err.message : String(err);
-          if (errorMsg.includes('rate limit') || errorMsg.includes('403')) {
-            rateLimitHit = true;
-            await sendNotification({
-              title: 'GitHub Rate Limit Reached',
-              body: 'You have hit the GitHub API rate limit. Consider adding a GitHub token to increase your limit from 60 to 5,000 requests per hour.'
-            });
-            setError('GitHub API rate limit reached. Please add a GitHub token or wait before retrying.');
-            break;
-          }
-          // Continue with other repositories even if one fails
         }
+      }),
+    ]);
 
-        // Add a small delay to avoid hitting rate limits
-        await new Promise(resolve => setTimeout(resolve, 100));
+    try {
+      const result = await invoke<BackendScanResult>('scan_awesome_list', {
+        url,
+        scanId,
+        resume,
+        includeReleases,
+        includeActivity
+      });
+      showScanResult(result);
+
+      if (result.cancelled) {
+        setError(`Scan cancelled after ${result.repositories.length} repositories. Scan the list again to resume it.`);
+        return;
       }
 
-      if (repos.length === 0 && !rateLimitHit) {
-        setError('Failed to fetch repository information. You may have hit the GitHub API rate limit.');
+      const rateLimitHit = result.failures.some(failure =>
+        failure.error?.includes('rate limit') || failure.error?.includes('403'));
+      if (rateLimitHit) {
         await sendNotification({
-          title: 'Scan Failed',
-          body: 'Failed to fetch repository information. You may have hit the GitHub API rate limit.'
+          title: 'GitHub Rate Limit Reached',
+          body: 'You have hit the GitHub API rate limit. Consider adding a GitHub token to increase your limit from 60 to 5,000 requests per hour.'
         });
+        setError('GitHub API rate limit reached. Please add a GitHub token or wait before retrying.');
+      }
+
+      if (result.repositories.length === 0) {
+        if (result.failures.length === 0) {
+          setError('No repositories found in this awesome list');
+        } else if (!rateLimitHit) {
+          setError('Failed to fetch repository information. You may have hit the GitHub API rate limit.');
+          await sendNotification({
+            title: 'Scan Failed',
+            body: 'Failed to fetch repository information. You may have hit the GitHub API rate limit.'
+          });
+        }
         return;
       }
 
       // Fetch when each repository was added to the awesome list
       try {
         setProgress({ current: 0, total: 1 });
+        const repoLinks = result.repositories.map(repo =>
+          (repo.linked_full_name ?? repo.full_name).split('/').slice(0, 2) as [string, string]);
         const addedDates = await invoke<Record<string, string>>('fetch_added_dates', {
           awesomeListUrl: url,
           repoLinks
         });
 
         // Update repositories with added dates
-        const reposWithDates = repos.map(repo => ({
+        setRepositories(result.repositories.map(repo => ({
           ...repo,
-          added_to_list_at: addedDates[repo.full_name] || null
-        }));
-
-        setRepositories(reposWithDates);
+          category: result.categories[categoryKey(repo)],
+          added_to_list_at: addedDates[repo.linked_full_name ?? repo.full_name] || null
+        })));
       } catch (err) {
         console.error('Failed to fetch added dates:', err);
 
@@ -111,13 +189,35 @@ export default function GitHubScanner() {
     } catch (err) {
       setError(err instanceof Error ? err.message : String(err));
     } finally {
+      unlisteners.forEach(unlisten => unlisten());
+      setActiveScanId(null);
       setIsScanning(false);
     }
   };
 
-  const handleExport = async (format: 'json' | 'csv') => {
+  const handleScoreHealth = async () => {
+    setIsScoring(true);
+    try {
+      const repos = repositories.map(repo => repo.full_name.split('/') as [string, string]);
+      const results = await invoke<HealthScoreResult[]>('score_repository_health', { repos });
+
+      const scores = new Map<string, HealthScoreResult>();
+      results.forEach(result => scores.set(`${result.owner}/${result.repo}`.toLowerCase(), result));
+      setRepositories(repositories.map(repo => ({
+        ...repo,
+        health: scores.get(repo.full_name.toLowerCase())?.health ?? repo.health
+      })));
+    } catch (err) {
+      console.error('Health scoring failed:', err);
+      setError('Failed to score repository health');
+    } finally {
+      setIsScoring(false);
+    }
+  };
+
+  const handleExport = async (format: ExportFormat) => {
     try {
-      const extension = format === 'json' ? 'json' : 'csv';
+      const extension = format === 'markdown' ? 'md' : format;
       const filePath = await save({
         filters: [{
           name: `${format.toUpperCase()} File`,
@@ -138,7 +238,8 @@ export default function GitHubScanner() {
           repositories,
           categories,
           format,
-          path: filePath
+          path: filePath,
+          scannedAt
         });
       }
     } catch (err) {
@@ -147,6 +248,37 @@ export default function GitHubScanner() {
     }
   };
 
+  const handleImport = async () => {
+    try {
+      const filePath = await open({
+        multiple: false,
+        filters: [{
+          name: 'Exported repositories',
+          extensions: ['json', 'ndjson', 'jsonl', 'csv']
+        }]
+      });
+
+      if (typeof filePath === 'string') {
+        const imported = await invoke<ImportedRepositories>('import_repositories', { path: filePath });
+        setRepositories(imported.repositories.map(repo => ({
+          ...repo,
+          category: imported.categories[repo.full_name]
+        })));
+        setCurrentListUrl('');
+        setScannedAt(null);
+        setError(imported.errors.length > 0
+          ? `Skipped ${imported.errors.length} invalid rows: ${imported.errors
+            .slice(0, 5)
+            .map(e => `row ${e.row}: ${e.message}`)
+            .join('; ')}`
+          : '');
+      }
+    } catch (err) {
+      console.error('Import failed:', err);
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
   const handleSaveToken = async () => {
     try {
       await invoke('set_github_token', { token: githubToken });
@@ -167,7 +299,7 @@ export default function GitHubScanner() {
         name: currentListName.trim(),
         url: currentListUrl,
         repositories,
-        lastScanned: new Date().toISOString(),
+        lastScanned: scannedAt ?? new Date().toISOString(),
         repositoryCount: repositories.length,
       };
 
@@ -182,6 +314,7 @@ export default function GitHubScanner() {
   const handleLoadList = (list: SavedList) => {
     setRepositories(list.repositories);
     setCurrentListUrl(list.url);
+    setScannedAt(list.lastScanned);
     setError('');
   };
 
@@ -189,12 +322,21 @@ export default function GitHubScanner() {
     <div className="p-8">
       <div className="flex items-center justify-between mb-6">
         <h1 className="text-3xl font-bold">GitHub Awesome Lists</h1>
-        <button
-          onClick={() => setShowTokenInput(!showTokenInput)}
-          className="px-4 py-2 bg-gray-600 text-white rounded-lg hover:bg-gray-700 transition-colors"
-        >
-          {showTokenInput ? 'Hide' : 'Set'} GitHub Token
-        </button>
+        <div className="flex gap-2">
+          <button
+            onClick={handleImport}
+            disabled={isScanning}
+            className="px-4 py-2 bg-gray-600 text-white rounded-lg hover:bg-gray-700 transition-colors disabled:opacity-50"
+          >
+            Import
+          </button>
+          <button
+            onClick={() => setShowTokenInput(!showTokenInput)}
+            className="px-4 py-2 bg-gray-600 text-white rounded-lg hover:bg-gray-700 transition-colors"
+          >
+            {showTokenInput ? 'Hide' : 'Set'} GitHub Token
+          </button>
+        </div>
       </div>
 
       {showTokenInput && (
@@ -234,20 +376,78 @@ export default function GitHubScanner() {
       <div className="bg-white dark:bg-gray-800 p-6 rounded-lg shadow mb-6">
         <InputForm onScan={handleScan} isScanning={isScanning} />
 
+        <label className="mt-3 flex items-center gap-2 text-sm text-gray-600 dark:text-gray-400">
+          <input
+            type="checkbox"
+            checked={includeReleases}
+            onChange={(e) => setIncludeReleases(e.target.checked)}
+            disabled={isScanning}
+          />
+          Include latest release and release count (one extra request per repository)
+        </label>
+
+        <label className="mt-1 flex items-center gap-2 text-sm text-gray-600 dark:text-gray-400">
+          <input
+            type="checkbox"
+            checked={includeActivity}
+            onChange={(e) => setIncludeActivity(e.target.checked)}
+            disabled={isScanning}
+          />
+          Include contributors, commit activity and languages (slower; GitHub may need time to compute statistics)
+        </label>
+
+        {resumePrompt && !isScanning && (
+          <div className="mt-4 p-4 bg-blue-50 dark:bg-blue-900/20 border border-blue-200 dark:border-blue-800 rounded-lg">
+            <p className="text-sm text-gray-700 dark:text-gray-300 mb-3">
+              A scan of this list started {new Date(resumePrompt.started_at).toLocaleString()} was interrupted
+              after {resumePrompt.completed} repositories.
+            </p>
+            <div className="flex gap-2">
+              <button
+                onClick={() => runScan(resumePrompt.list_url, true)}
+                className="px-4 py-2 bg-blue-600 text-white rounded-lg hover:bg-blue-700 transition-colors"
+              >
+                Resume
+              </button>
+              <button
+                onClick={() => runScan(resumePrompt.list_url, false)}
+                className="px-4 py-2 bg-gray-500 text-white rounded-lg hover:bg-gray-600 transition-colors"
+              >
+                Start Over
+              </button>
+              <button
+                onClick={() => setResumePrompt(null)}
+                className="px-4 py-2 text-gray-600 dark:text-gray-400 hover:underline"
+              >
+                Dismiss
+              </button>
+            </div>
+          </div>
+        )}
+
         {isScanning && (
           <div className="mt-4">
             <div className="flex items-center justify-between mb-2">
               <span className="text-sm text-gray-600 dark:text-gray-400">
                 Scanning repositories... {progress.current} / {progress.total}
               </span>
-              <span className="text-sm text-gray-600 dark:text-gray-400">
-                {Math.round((progress.current / progress.total) * 100)}%
-              </span>
+              <div className="flex items-center gap-3">
+                <span className="text-sm text-gray-600 dark:text-gray-400">
+                  {progress.total > 0 ? Math.round((progress.current / progress.total) * 100) : 0}%
+                </span>
+                <button
+                  onClick={handleCancelScan}
+                  disabled={!activeScanId}
+                  className="px-3 py-1 text-sm bg-red-600 text-white rounded-lg hover:bg-red-700 transition-colors disabled:opacity-50"
+                >
+                  Cancel
+                </button>
+              </div>
             </div>
             <div className="w-full bg-gray-200 dark:bg-gray-700 rounded-full h-2">
               <div
                 className="bg-blue-600 h-2 rounded-full transition-all duration-300"
-                style={{ width: `${(progress.current / progress.total) * 100}%` }}
+                style={{ width: `${progress.total > 0 ? (progress.current / progress.total) * 100 : 0}%` }}
               ></div>
             </div>
           </div>
@@ -262,7 +462,15 @@ export default function GitHubScanner() {
 
       {repositories.length > 0 && (
         <>
-          <div className="mb-4 flex justify-end">
+          <div className="mb-4 flex justify-end gap-2">
+            <button
+              onClick={handleScoreHealth}
+              disabled={isScoring || isScanning}
+              className="px-4 py-2 bg-purple-600 text-white rounded-lg hover:bg-purple-700 transition-colors disabled:opacity-50"
+              title="Six GitHub requests per repository"
+            >
+              {isScoring ? 'Scoring…' : '❤️ Score Health'}
+            </button>
             <button
               onClick={handleSaveList}
               className="px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 transition-colors flex items-center gap-2"
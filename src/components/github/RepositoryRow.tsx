@@ -41,6 +41,22 @@ export default function RepositoryRow({ repository }: RepositoryRowProps) {
                 📂 {repository.category}
               </span>
             )}
+            {repository.health && (
+              <span
+                className={`text-xs px-2 py-1 rounded-full ${
+                  repository.health.score >= 70
+                    ? 'bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200'
+                    : repository.health.score >= 40
+                      ? 'bg-yellow-100 dark:bg-yellow-900 text-yellow-800 dark:text-yellow-200'
+                      : 'bg-red-100 dark:bg-red-900 text-red-800 dark:text-red-200'
+                }`}
+                title={repository.health.components
+                  .map(c => `${c.detail}: ${c.points}/${c.weight}`)
+                  .join('\n')}
+              >
+                ❤️ {repository.health.score}
+              </span>
+            )}
             {repository.homepage && (
               <a
                 href={repository.homepage}
@@ -93,8 +109,74 @@ export default function RepositoryRow({ repository }: RepositoryRowProps) {
                 <span>Added {formatDate(repository.added_to_list_at)}</span>
               </div>
             )}
+
+            {repository.latest_release && (
+              <div
+                className="flex items-center gap-1"
+                title={repository.latest_release.name ?? repository.latest_release.tag_name}
+              >
+                <span>🏷️</span>
+                <span>{repository.latest_release.tag_name}</span>
+                {repository.latest_release.prerelease && (
+                  <span className="text-xs px-1 bg-orange-100 dark:bg-orange-900 text-orange-800 dark:text-orange-200 rounded">
+                    pre-release
+                  </span>
+                )}
+                {repository.latest_release.published_at && (
+                  <span className="text-gray-500 dark:text-gray-400">
+                    {formatDate(repository.latest_release.published_at)}
+                  </span>
+                )}
+                {repository.release_count != null && (
+                  <span className="text-gray-500 dark:text-gray-400">
+                    ({repository.release_count} releases)
+                  </span>
+                )}
+              </div>
+            )}
           </div>
 
+          {repository.activity && (
+            <div className="flex flex-wrap items-center gap-4 mt-2 text-sm text-gray-600 dark:text-gray-400">
+              {repository.activity.contributor_count != null && (
+                <div className="flex items-center gap-1">
+                  <span>👥</span>
+                  <span>{repository.activity.contributor_count} contributors</span>
+                </div>
+              )}
+
+              {repository.activity.top_contributor_share != null && (
+                <div title="Share of commits by the most active contributor">
+                  Top contributor: {Math.round(repository.activity.top_contributor_share * 100)}%
+                </div>
+              )}
+
+              {repository.activity.weekly_commits && (
+                <div
+                  className="flex items-end gap-px h-5"
+                  title={`${repository.activity.weekly_commits.reduce((sum, n) => sum + n, 0)} commits in the last year`}
+                >
+                  {repository.activity.weekly_commits.map((count, i) => (
+                    <span
+                      key={i}
+                      className="w-0.5 bg-green-500"
+                      style={{ height: `${(count / Math.max(1, ...repository.activity!.weekly_commits!)) * 100}%` }}
+                    ></span>
+                  ))}
+                </div>
+              )}
+
+              {repository.activity.languages.length > 0 && (
+                <div>
+                  {repository.activity.languages.slice(0, 3).map(l => {
+                    const total = repository.activity!.languages.reduce((sum, lang) => sum + lang.bytes, 0);
+                    return `${l.name} ${Math.round((l.bytes / total) * 100)}%`;
+                  }).join(' · ')}
+                </div>
+              )}
+            </div>
+          )}
+
           {repository.topics.length > 0 && (
             <div className="flex flex-wrap gap-2 mt-3">
               {repository.topics.map(topic => (
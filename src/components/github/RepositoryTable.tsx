@@ -2,12 +2,14 @@ import { useState, useMemo } from 'react';
 import type { Repository } from '../../types';
 import RepositoryRow from './RepositoryRow';
 
+export type ExportFormat = 'json' | 'ndjson' | 'csv' | 'markdown' | 'html' | 'sqlite';
+
 interface RepositoryTableProps {
   repositories: Repository[];
-  onExport: (format: 'json' | 'csv') => void;
+  onExport: (format: ExportFormat) => void;
 }
 
-type SortField = 'name' | 'stars' | 'updated_at' | 'added_to_list';
+type SortField = 'name' | 'stars' | 'updated_at' | 'added_to_list' | 'health';
 type SortOrder = 'asc' | 'desc';
 
 export default function RepositoryTable({ repositories, onExport }: RepositoryTableProps) {
@@ -18,6 +20,7 @@ export default function RepositoryTable({ repositories, onExport }: RepositoryTa
   const [licenseFilter, setLicenseFilter] = useState('');
   const [categoryFilter, setCategoryFilter] = useState('');
   const [minStars, setMinStars] = useState(0);
+  const [minHealth, setMinHealth] = useState(0);
 
   // Get unique languages, licenses, and categories for filters
   const languages = useMemo(() => {
@@ -55,8 +58,10 @@ export default function RepositoryTable({ repositories, onExport }: RepositoryTa
       const matchesLicense = licenseFilter === '' || repo.license?.name === licenseFilter;
       const matchesCategory = categoryFilter === '' || repo.category === categoryFilter;
       const matchesStars = repo.stargazers_count >= minStars;
+      // Unscored repositories only pass when no minimum is set
+      const matchesHealth = minHealth === 0 || (repo.health?.score ?? -1) >= minHealth;
 
-      return matchesSearch && matchesLanguage && matchesLicense && matchesCategory && matchesStars;
+      return matchesSearch && matchesLanguage && matchesLicense && matchesCategory && matchesStars && matchesHealth;
     });
 
     // Sort
@@ -85,13 +90,25 @@ export default function RepositoryTable({ repositories, onExport }: RepositoryTa
             comparison = new Date(a.added_to_list_at).getTime() - new Date(b.added_to_list_at).getTime();
           }
           break;
+        case 'health':
+          // Unscored repos go to the end
+          if (!a.health && !b.health) {
+            comparison = 0;
+          } else if (!a.health) {
+            comparison = sortOrder === 'asc' ? 1 : -1;
+          } else if (!b.health) {
+            comparison = sortOrder === 'asc' ? -1 : 1;
+          } else {
+            comparison = a.health.score - b.health.score;
+          }
+          break;
       }
 
       return sortOrder === 'asc' ? comparison : -comparison;
     });
 
     return filtered;
-  }, [repositories, sortField, sortOrder, searchQuery, languageFilter, licenseFilter, categoryFilter, minStars]);
+  }, [repositories, sortField, sortOrder, searchQuery, languageFilter, licenseFilter, categoryFilter, minStars, minHealth]);
 
   const handleSort = (field: SortField) => {
     if (sortField === field) {
@@ -162,6 +179,18 @@ export default function RepositoryTable({ repositories, onExport }: RepositoryTa
               className="w-24 px-2 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 dark:bg-gray-700"
             />
           </div>
+
+          <div className="flex items-center gap-2">
+            <label className="text-sm">Min Health:</label>
+            <input
+              type="number"
+              value={minHealth}
+              onChange={(e) => setMinHealth(Number(e.target.value))}
+              min="0"
+              max="100"
+              className="w-20 px-2 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 dark:bg-gray-700"
+            />
+          </div>
         </div>
 
         <div className="flex justify-between items-center">
@@ -182,6 +211,30 @@ export default function RepositoryTable({ repositories, onExport }: RepositoryTa
             >
               Export CSV
             </button>
+            <button
+              onClick={() => onExport('markdown')}
+              className="px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 transition-colors"
+            >
+              Export Markdown
+            </button>
+            <button
+              onClick={() => onExport('html')}
+              className="px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 transition-colors"
+            >
+              Export HTML
+            </button>
+            <button
+              onClick={() => onExport('ndjson')}
+              className="px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 transition-colors"
+            >
+              Export NDJSON
+            </button>
+            <button
+              onClick={() => onExport('sqlite')}
+              className="px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 transition-colors"
+            >
+              Export SQLite
+            </button>
           </div>
         </div>
       </div>
@@ -220,6 +273,14 @@ export default function RepositoryTable({ repositories, onExport }: RepositoryTa
         >
           Added to List {sortField === 'added_to_list' && (sortOrder === 'asc' ? '↑' : '↓')}
         </button>
+        <button
+          onClick={() => handleSort('health')}
+          className={`px-4 py-2 rounded-lg ${
+            sortField === 'health' ? 'bg-blue-600 text-white' : 'bg-gray-200 dark:bg-gray-700'
+          }`}
+        >
+          Health {sortField === 'health' && (sortOrder === 'asc' ? '↑' : '↓')}
+        </button>
       </div>
 
       {/* Repository List */}
@@ -230,7 +291,7 @@ export default function RepositoryTable({ repositories, onExport }: RepositoryTa
           </div>
         ) : (
           filteredAndSortedRepos.map(repo => (
-            <RepositoryRow key={repo.id} repository={repo} />
+            <RepositoryRow key={repo.full_name} repository={repo} />
           ))
         )}
       </div>
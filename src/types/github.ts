@@ -14,7 +14,80 @@ export interface Repository {
   updated_at: string;
   created_at: string;
   added_to_list_at: string | null;
+  archived?: boolean;
+  disabled?: boolean;
+  fork?: boolean;
+  is_template?: boolean;
+  linked_full_name?: string | null;
+  latest_release?: Release | null;
+  release_count?: number | null;
+  activity?: RepositoryActivity | null;
+  pushed_at?: string | null;
   category?: string;
+  health?: HealthScore;
+}
+
+export interface RepositoryActivity {
+  contributor_count: number | null;
+  top_contributor_share: number | null;
+  weekly_commits: number[] | null;
+  languages: LanguageBytes[];
+}
+
+export interface LanguageBytes {
+  name: string;
+  bytes: number;
+}
+
+export interface HealthSignals {
+  pushed_at: string | null;
+  recent_commits: number;
+  open_issues: number;
+  closed_issues: number;
+  release_count: number;
+  latest_release_at: string | null;
+  contributors: number | null;
+  archived: boolean;
+  has_ci: boolean;
+}
+
+export interface HealthComponent {
+  signal: string;
+  weight: number;
+  points: number;
+  detail: string;
+}
+
+export interface HealthScore {
+  full_name: string;
+  score: number;
+  components: HealthComponent[];
+  signals: HealthSignals;
+}
+
+export interface HealthScoreResult {
+  owner: string;
+  repo: string;
+  health: HealthScore | null;
+  error: string | null;
+}
+
+export interface ImportError {
+  row: number;
+  message: string;
+}
+
+export interface ImportedRepositories {
+  repositories: Repository[];
+  categories: Record<string, string>;
+  errors: ImportError[];
+}
+
+export interface Release {
+  tag_name: string;
+  name: string | null;
+  published_at: string | null;
+  prerelease: boolean;
 }
 
 export interface License {
@@ -24,7 +97,43 @@ export interface License {
   url: string | null;
 }
 
+export type Forge = 'github' | 'gitlab' | 'gitea' | 'sourcehut' | 'bitbucket';
+
+export interface AwesomeEntry {
+  owner: string;
+  repo: string;
+  forge: Forge;
+  host: string;
+  name: string;
+  description: string | null;
+  category_path: string[];
+}
+
+export interface CrawledEntry extends AwesomeEntry {
+  parent_list: string;
+  depth: number;
+  is_list: boolean;
+}
+
+export interface ListSection {
+  title: string;
+  anchor: string;
+  depth: number;
+  sections: ListSection[];
+  entries: AwesomeEntry[];
+}
+
+export interface RepositoryFetchResult {
+  host: string;
+  owner: string;
+  repo: string;
+  repository: Repository | null;
+  error: string | null;
+  unavailable?: boolean;
+}
+
 export interface ScanProgress {
+  scanId?: string;
   total: number;
   current: number;
   currentRepo: string;
@@ -36,6 +145,115 @@ export interface ScanResult {
   scanDate: string;
 }
 
+export interface ScanStarted {
+  scan_id: string;
+  list_url: string;
+  total: number;
+  resumed: number;
+}
+
+export interface ScanCheckpointSummary {
+  list_url: string;
+  started_at: string;
+  completed: number;
+}
+
+export interface BackendScanResult {
+  scan_id: string;
+  list_url: string;
+  scanned_at: string;
+  cancelled: boolean;
+  repositories: Repository[];
+  categories: Record<string, string>;
+  failures: RepositoryFetchResult[];
+}
+
+export type MaintenanceIssueKind = 'renamed' | 'transferred' | 'archived' | 'disabled' | 'deleted' | 'fork';
+
+export type MaintenanceAction = 'update' | 'remove' | 'review';
+
+export interface MaintenanceIssue {
+  linked: string;
+  category: string | null;
+  kind: MaintenanceIssueKind;
+  action: MaintenanceAction;
+  canonical_full_name: string | null;
+  canonical_url: string | null;
+  detail: string;
+}
+
+export interface MaintenanceReport {
+  scan_id: string;
+  list_url: string;
+  scanned_at: string;
+  issues: MaintenanceIssue[];
+}
+
+export type LintSeverity = 'error' | 'warning';
+
+export interface LintDiagnostic {
+  rule: string;
+  severity: LintSeverity;
+  message: string;
+  line: number;
+  column: number;
+}
+
+export type LinkStatus = 'ok' | 'redirected' | 'broken' | 'error';
+
+export interface LinkCheckResult {
+  url: string;
+  sections: string[];
+  line: number | null;
+  repositories: string[];
+  status: LinkStatus;
+  http_status: number | null;
+  redirects: string[];
+  error: string | null;
+}
+
+export interface ScanSummary {
+  scan_id: string;
+  list_url: string;
+  scanned_at: string;
+  cancelled: boolean;
+  repository_count: number;
+  failure_count: number;
+}
+
+export interface MetricPoint {
+  scan_id: string;
+  scanned_at: string;
+  stargazers_count: number;
+  forks_count: number;
+  open_issues_count: number;
+  archived: boolean;
+}
+
+export interface RepositoryTrend {
+  full_name: string;
+  points: MetricPoint[];
+}
+
+export interface RepositoryDelta {
+  full_name: string;
+  stargazers_count: number;
+  stars_gained: number;
+  forks_gained: number;
+  issues_opened: number;
+  newly_archived: boolean;
+  is_new: boolean;
+}
+
+export interface ScanComparison {
+  from_scan_id: string;
+  to_scan_id: string;
+  from_scanned_at: string;
+  to_scanned_at: string;
+  deltas: RepositoryDelta[];
+  removed: string[];
+}
+
 export interface SavedList {
   id: string;
   name: string;
@@ -44,3 +262,17 @@ export interface SavedList {
   lastScanned: string;
   repositoryCount: number;
 }
+
+export interface RateLimitInfo {
+  resource: string;
+  limit: number;
+  remaining: number;
+  used: number;
+  reset: number;
+}
+
+export interface RateLimitStatus {
+  authenticated: boolean;
+  resources: RateLimitInfo[];
+  scan_wait_seconds: number | null;
+}